@@ -0,0 +1,169 @@
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use colored::Colorize;
+use toml_edit::{DocumentMut, Item, Table, value};
+
+use crate::runner::Answer;
+
+/// Accepted answers for a year, stored in `aocYYYY/answers.toml` as
+/// ```toml
+/// [day01]
+/// part1 = 1189304
+/// part2 = "ABCDEF"
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl Answers {
+    /// Load the answers for a year. A missing file gives an empty set of answers.
+    pub fn for_year(year: usize) -> io::Result<Self> {
+        Self::load(format!("aoc{year}/answers.toml"))
+    }
+
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        let path = path.into();
+        let doc = match fs::read_to_string(&path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => DocumentMut::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, doc })
+    }
+
+    fn keys(day: usize, part: usize) -> (String, String) {
+        (format!("day{day:02}"), format!("part{part}"))
+    }
+
+    /// The accepted answer for a part, if there is one.
+    pub fn get(&self, day: usize, part: usize) -> Option<String> {
+        let (day, part) = Self::keys(day, part);
+        let item = self.doc.get(&day)?.get(&part)?;
+        match item.as_str() {
+            Some(s) => Some(s.to_string()),
+            None => item.as_value().map(|v| v.to_string().trim().to_string()),
+        }
+    }
+
+    /// Compare a part's result with the accepted answer.
+    pub fn check<E>(&self, day: usize, part: usize, result: &Result<Answer, E>) -> Check {
+        match (self.get(day, part), result) {
+            (None, Ok(answer)) if *answer != Answer::Unsolved => Check::New,
+            (None, _) => Check::Unchecked,
+            (Some(expected), Ok(answer)) if expected == answer.to_string() => Check::Pass,
+            (Some(expected), _) => Check::Fail(expected),
+        }
+    }
+
+    /// Store an answer unless the part already has one. Returns true if it was stored.
+    pub fn record(&mut self, day: usize, part: usize, answer: &Answer) -> bool {
+        let (day, part) = Self::keys(day, part);
+        let table = self
+            .doc
+            .entry(&day)
+            .or_insert(Item::Table(Table::new()))
+            .as_table_like_mut()
+            .expect("Answers must be stored in tables");
+        if table.contains_key(&part) {
+            return false;
+        }
+        let stored = match answer {
+            Answer::Unsolved => return false,
+            Answer::Int(v) => value(*v),
+            Answer::UInt(v) => match i64::try_from(*v) {
+                Ok(v) => value(v),
+                Err(_) => value(v.to_string()),
+            },
+            Answer::Text(v) | Answer::Art(v) => value(v.as_str()),
+        };
+        table.insert(&part, stored);
+        true
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(&self.path, self.doc.to_string())
+    }
+}
+
+/// How a part's result compares with the accepted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    /// Holds the accepted answer.
+    Fail(String),
+    /// Solved but there's no accepted answer yet.
+    New,
+    /// Nothing to compare.
+    Unchecked,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Pass => write!(f, "{}", "[pass]".green()),
+            Check::Fail(expected) => {
+                write!(f, "{}", format!("[FAIL expected {expected}]").red())
+            }
+            Check::New => write!(f, "{}", "[new]".yellow()),
+            Check::Unchecked => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str =
+        "[day01]\npart1 = 11\npart2 = \"31\"\n\n[day13]\npart2 = \"\"\"\n#..\n.#.\"\"\"\n";
+
+    fn answers() -> Answers {
+        Answers {
+            path: PathBuf::new(),
+            doc: ANSWERS.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let answers = answers();
+        assert_eq!(Some("11".into()), answers.get(1, 1));
+        assert_eq!(Some("31".into()), answers.get(1, 2));
+        assert_eq!(Some("#..\n.#.".into()), answers.get(13, 2));
+        assert_eq!(None, answers.get(2, 1));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = answers();
+        let ok = |a: Answer| Ok::<_, ()>(a);
+        assert_eq!(Check::Pass, answers.check(1, 1, &ok(Answer::UInt(11))));
+        assert_eq!(
+            Check::Fail("31".into()),
+            answers.check(1, 2, &ok(Answer::UInt(30)))
+        );
+        assert_eq!(Check::Fail("11".into()), answers.check(1, 1, &Err(())));
+        assert_eq!(
+            Check::Pass,
+            answers.check(13, 2, &ok(Answer::Art("#..\n.#.".into())))
+        );
+        assert_eq!(Check::New, answers.check(2, 1, &ok(Answer::Int(-4))));
+        assert_eq!(Check::Unchecked, answers.check(2, 1, &ok(Answer::Unsolved)));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut answers = answers();
+        assert!(!answers.record(1, 1, &Answer::UInt(12)));
+        assert!(answers.record(1, 3, &Answer::UInt(12)));
+        assert!(answers.record(2, 1, &Answer::Text("abc".into())));
+        assert!(!answers.record(2, 2, &Answer::Unsolved));
+        assert_eq!(Some("11".into()), answers.get(1, 1));
+        assert_eq!(Some("12".into()), answers.get(1, 3));
+        assert_eq!(Some("abc".into()), answers.get(2, 1));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{hint::black_box, io::Write, time::Duration};
+
+use crate::runner::{Day, timed};
+
+/// How many times each day is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Untimed runs before sampling starts.
+    pub warmup: usize,
+    /// Timed runs.
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            iterations: 10,
+        }
+    }
+}
+
+/// Summary of a phase's samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+}
+
+impl Stats {
+    /// None if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        nanos.sort_by(f64::total_cmp);
+
+        let len = nanos.len();
+        let median = if len % 2 == 0 {
+            (nanos[len / 2 - 1] + nanos[len / 2]) / 2.0
+        } else {
+            nanos[len / 2]
+        };
+        let mean = nanos.iter().sum::<f64>() / len as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len as f64;
+        Some(Self {
+            samples: len,
+            min: nanos[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    fn to_json(self) -> String {
+        format!(
+            "{{\"samples\": {}, \"min_ns\": {:.0}, \"median_ns\": {:.0}, \"mean_ns\": {:.0}, \"stddev_ns\": {:.0}}}",
+            self.samples, self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+/// Timings for each phase of a day. A phase that never completed has no stats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bench {
+    pub year: usize,
+    pub day: usize,
+    pub parse: Option<Stats>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    /// Why benchmarking stopped early.
+    pub failure: Option<String>,
+}
+
+impl Bench {
+    fn to_json(&self) -> String {
+        let stats = |s: Option<Stats>| s.map_or("null".to_string(), Stats::to_json);
+        let failure = match &self.failure {
+            Some(f) => format!("\"{}\"", escape(f)),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"year\": {}, \"day\": {}, \"parse\": {}, \"part1\": {}, \"part2\": {}, \"failure\": {failure}}}",
+            self.year,
+            self.day,
+            stats(self.parse),
+            stats(self.part1),
+            stats(self.part2)
+        )
+    }
+}
+
+/// Benchmark a day, building a fresh solution from `input` for every run.
+/// Stops at the first failing phase, keeping the samples taken so far.
+pub fn bench_day(day: &Day, input: &str, options: &BenchOptions) -> Bench {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    let mut failure = None;
+    for run in 0..options.warmup + options.iterations {
+        match iteration(day, input) {
+            Ok(durations) if run >= options.warmup => {
+                for (phase, duration) in samples.iter_mut().zip(durations) {
+                    phase.push(duration);
+                }
+            }
+            Ok(_) => (),
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+    let [parse, part1, part2] = samples.map(|s| Stats::from_samples(&s));
+    Bench {
+        year: day.year,
+        day: day.day,
+        parse,
+        part1,
+        part2,
+        failure,
+    }
+}
+
+fn iteration(day: &Day, input: &str) -> Result<[Duration; 3], String> {
+    let mut solution = day.build(input);
+    let parse = timed(|| solution.parse());
+    parse.result.map_err(|e| format!("Parsing: {e}"))?;
+    let part1 = timed(|| solution.part1().map(black_box));
+    part1.result.map_err(|e| format!("Part 1: {e}"))?;
+    let part2 = timed(|| solution.part2().map(black_box));
+    part2.result.map_err(|e| format!("Part 2: {e}"))?;
+    Ok([parse.duration, part1.duration, part2.duration])
+}
+
+pub fn print_bench(bench: &Bench, out: &mut dyn Write) {
+    let _ = writeln!(out, "---- {}: {:02} ----", bench.year, bench.day);
+    for (label, stats) in [
+        ("Parsing", bench.parse),
+        ("Part 1 ", bench.part1),
+        ("Part 2 ", bench.part2),
+    ] {
+        let _ = match stats {
+            Some(s) => writeln!(
+                out,
+                "{label}  min {:>9}  median {:>9}  mean {:>9}  stddev {:>9}",
+                format_nanos(s.min),
+                format_nanos(s.median),
+                format_nanos(s.mean),
+                format_nanos(s.stddev)
+            ),
+            None => writeln!(out, "{label}  no samples"),
+        };
+    }
+    if let Some(failure) = &bench.failure {
+        let _ = writeln!(out, "Stopped: {failure}");
+    }
+}
+
+/// Nanoseconds in the largest unit that keeps the value at least 1.
+pub fn format_nanos(nanos: f64) -> String {
+    if nanos < 1e3 {
+        format!("{nanos:.0}ns")
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.3}s", nanos / 1e9)
+    }
+}
+
+/// All the benchmarks of a run as a JSON document.
+pub fn to_json(options: &BenchOptions, benches: &[Bench]) -> String {
+    let days = benches
+        .iter()
+        .map(|b| format!("    {}", b.to_json()))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!(
+        "{{\n  \"warmup\": {},\n  \"iterations\": {},\n  \"days\": [\n{days}\n  ]\n}}\n",
+        options.warmup, options.iterations
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::{Answer, Error, Registry, Solution};
+
+    use super::*;
+
+    struct Counter {
+        day: usize,
+        input: String,
+    }
+
+    impl Solution for Counter {
+        fn name(&self) -> (usize, usize) {
+            (2015, self.day)
+        }
+
+        fn parse(&mut self) -> Result<(), Error> {
+            match self.input.as_str() {
+                "missing.txt" => Err("no input".into()),
+                _ => Ok(()),
+            }
+        }
+
+        fn part1(&mut self) -> Result<Answer, Error> {
+            Ok(Answer::UInt(1))
+        }
+
+        fn part2(&mut self) -> Result<Answer, Error> {
+            if self.day == 2 {
+                Err("no \"part 2\"".into())
+            } else {
+                Ok(Answer::UInt(2))
+            }
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 7].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(4, stats.samples);
+        assert_eq!(1.0, stats.min);
+        assert_eq!(4.0, stats.median);
+        assert_eq!(4.0, stats.mean);
+        assert_eq!(5f64.sqrt(), stats.stddev);
+        assert_eq!(3.0, Stats::from_samples(&samples[1..]).unwrap().median);
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!("850ns", format_nanos(850.0));
+        assert_eq!("12.3µs", format_nanos(12_340.0));
+        assert_eq!("4.57ms", format_nanos(4_567_000.0));
+        assert_eq!("2.000s", format_nanos(2e9));
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let mut registry = Registry::new();
+        registry.register(|input| Box::new(Counter { day: 1, input }));
+        registry.register(|input| Box::new(Counter { day: 2, input }));
+        let options = BenchOptions {
+            warmup: 2,
+            iterations: 5,
+        };
+
+        let day = registry.get(1).unwrap();
+        let bench = bench_day(day, "input.txt", &options);
+        assert_eq!((2015, 1), (bench.year, bench.day));
+        assert_eq!(Some(5), bench.part2.map(|s| s.samples));
+        assert_eq!(None, bench.failure);
+        let mut out = Vec::new();
+        print_bench(&bench, &mut out);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("---- 2015: 01 ----\nParsing  min "));
+
+        // Day 2 fails in its first warm up run.
+        let missing = bench_day(day, "missing.txt", &options);
+        assert_eq!(Some("Parsing: FAILED no input".into()), missing.failure);
+
+        let bench = bench_day(registry.get(2).unwrap(), "input.txt", &options);
+        assert_eq!(None, bench.part1);
+        assert_eq!(Some("Part 2: FAILED no \"part 2\"".into()), bench.failure);
+        let json = to_json(&options, &[bench]);
+        assert!(json.contains("\"warmup\": 2,"));
+        assert!(json.contains(
+            "{\"year\": 2015, \"day\": 2, \"parse\": null, \"part1\": null, \"part2\": null, \"failure\": \"Part 2: FAILED no \\\"part 2\\\"\"}"
+        ));
+    }
+}
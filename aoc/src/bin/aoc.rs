@@ -0,0 +1,84 @@
+use std::{
+    env,
+    path::Path,
+    process::{self, Command as Process},
+};
+
+use aoc::driver::{Cli, Command, Layout, Year, workspace_years};
+
+const USAGE: &str = "Usage: aoc <command> [year] [day] [flags]
+
+Commands:
+    run <year> [day] [--part N] [--input PATH] [--parallel] [--jobs N] [--timeout SECS]
+    bench <year> [day] [--input PATH] [--warmup N] [--iterations N] [--json PATH]
+    verify [year] [day] [--parallel] [--jobs N] [--timeout SECS]
+    list [year]
+
+No day runs the most recent day and day 0 runs every day.
+Run from the workspace root.";
+
+fn main() {
+    let cli = match Cli::parse(env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            println!("{e}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    let root = Path::new(".");
+    let years = match cli.year {
+        Some(year) => vec![year],
+        None if matches!(cli.command, Command::Verify | Command::List) => workspace_years(root)
+            .unwrap_or_else(|e| {
+                println!("Could not read the workspace Cargo.toml: {e}");
+                process::exit(2);
+            }),
+        None => {
+            println!("Missing year\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for year in years {
+        let year = match Year::load(root, year) {
+            Ok(year) => year,
+            Err(e) => {
+                println!("Could not load aoc{year}: {e}");
+                failed = true;
+                continue;
+            }
+        };
+        match cli.command {
+            Command::List => println!("{year}"),
+            // Only the runner years can check their answers.
+            Command::Verify if cli.year.is_none() && year.layout == Layout::Bins => (),
+            _ => failed |= !dispatch(&year, &cli),
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Run the cargo commands for a year. Returns false if any of them failed.
+fn dispatch(year: &Year, cli: &Cli) -> bool {
+    let commands = match year.cargo_args(cli) {
+        Ok(commands) => commands,
+        Err(e) => {
+            println!("{e}");
+            return false;
+        }
+    };
+    let mut success = true;
+    for args in commands {
+        match Process::new("cargo").args(&args).status() {
+            Ok(status) => success &= status.success(),
+            Err(e) => {
+                println!("Could not start cargo: {e}");
+                return false;
+            }
+        }
+    }
+    success
+}
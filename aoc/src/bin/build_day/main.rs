@@ -0,0 +1,1106 @@
+use aoc::{
+    driver::{Layout, Year},
+    puzzle,
+    testing::Example,
+};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use reqwest::{
+    Url,
+    blocking::{Client, ClientBuilder},
+};
+use reqwest_cookie_store::{CookieStore, CookieStoreMutex, RawCookie};
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use std::{fs::create_dir, io::Read};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table, value};
+
+#[cfg(test)]
+mod mock_server;
+
+const URL: &str = "https://adventofcode.com";
+/// Overrides `URL`, for example to point at a local mock server.
+const URL_VAR: &str = "AOC_BASE_URL";
+/// The session cookie of a logged in user, usually set in .env.
+const SESSION_VAR: &str = "ADVENT_OF_CODE_SESSION";
+/// Sent with every request so the site can tell who is asking.
+const USER_AGENT: &str = concat!(
+    "build_day/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/MrRobotMan aoc tooling)"
+);
+/// Least time between two requests.
+const THROTTLE: Duration = Duration::from_secs(2);
+/// Where the day templates live, relative to the workspace root.
+const TEMPLATES: &str = "templates";
+/// Argument that creates a year's crate instead of a day.
+const NEW_YEAR: &str = "new-year";
+/// Argument that extracts a day's examples from its puzzle page instead.
+const EXAMPLES: &str = "examples";
+const PREVIEW: usize = 50;
+const LINES: usize = 5;
+
+fn main() {
+    // Before any lookup, so .env can set AOC_BASE_URL too. Only downloads need it,
+    // where a missing .env shows up as a missing session.
+    let _ = dotenv::dotenv();
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    if args.first().is_some_and(|a| a == NEW_YEAR) {
+        match args.get(1).and_then(|year| year.parse().ok()) {
+            Some(year) => match new_year(Path::new("."), year) {
+                Ok(steps) => steps.iter().for_each(|step| println!("{step}")),
+                Err(e) => println!("Could not create aoc{year}. {e}"),
+            },
+            None => println!("Invalid arguments. Please supply a year: {NEW_YEAR} YEAR"),
+        }
+        return;
+    }
+    if args.first().is_some_and(|a| a == EXAMPLES) {
+        args.remove(0);
+        let page = take_flag(&mut args, "--html");
+        let url = base_url(take_flag(&mut args, "--url"));
+        let Some((year, day)) = get_args(&args).filter(|(_, day)| *day > 0) else {
+            println!("Invalid arguments. Please supply year and day: {EXAMPLES} YEAR DAY");
+            return;
+        };
+        let html = match page {
+            Some(path) => {
+                fs::read_to_string(&path).map_err(|e| format!("Could not read {path}. {e}"))
+            }
+            None => connect(url).and_then(|mut fetcher| {
+                fetcher
+                    .puzzle(year, day, Utc::now())
+                    .map_err(|e| e.to_string())
+            }),
+        };
+        match html {
+            Ok(html) => build_examples(year, day, &html),
+            Err(e) => println!("{e}"),
+        }
+        return;
+    }
+
+    let url = base_url(take_flag(&mut args, "--url"));
+    let layout = take_flag(&mut args, "--layout");
+    let (year, day) = match get_args(&args) {
+        None => {
+            println!("Invalid arguments. Please supply year and day");
+            return;
+        }
+        Some((year, day)) => (year, day),
+    };
+    let Some(layout) = get_layout(layout, year) else {
+        println!("Layout must be bin or runner");
+        return;
+    };
+
+    let mut fetcher = match connect(url) {
+        Ok(fetcher) => fetcher,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    if day == 0 {
+        for day in 1..=25 {
+            build_day(year, day, &mut fetcher, &layout);
+        }
+    } else {
+        build_day(year, day, &mut fetcher, &layout);
+    }
+}
+
+/// A fetcher logged in with the session from .env.
+fn connect(url: String) -> Result<Fetcher, String> {
+    let cookie_store = load_cookies(&url)?;
+    let cookie_store = Arc::new(CookieStoreMutex::new(cookie_store));
+    Ok(Fetcher::new(build_client(cookie_store), url))
+}
+
+fn build_client(cookie_store: Arc<CookieStoreMutex>) -> Client {
+    ClientBuilder::new()
+        .cookie_provider(cookie_store)
+        .user_agent(USER_AGENT)
+        .build()
+        .unwrap()
+}
+
+/// The site to download from: `--url`, then `AOC_BASE_URL`, then the real site.
+fn base_url(flag: Option<String>) -> String {
+    flag.or_else(|| env::var(URL_VAR).ok())
+        .unwrap_or(URL.into())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Remove a flag and its value from the arguments.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let idx = args.iter().position(|a| a == flag)?;
+    args.remove(idx);
+    (idx < args.len()).then(|| args.remove(idx))
+}
+
+fn build_day(year: i32, day: u32, fetcher: &mut Fetcher, layout: &Layout) {
+    let file = PathBuf::from(&format!("aoc{year}/inputs/day{day:02}.txt"));
+    match fetcher.fetch(year, day, &file, Utc::now()) {
+        Ok(None) => println!("Input for {year} {day} already downloaded."),
+        Ok(Some(text)) => {
+            show_preview(&text);
+            let manifest = Manifest::for_year(year).and_then(|mut manifest| {
+                manifest.record(day, Utc::now());
+                manifest.save()
+            });
+            if let Err(e) = manifest {
+                println!("Could not update the input manifest. {e}");
+            }
+            write_file(file, text);
+            println!("Created input.");
+        }
+        Err(InputResult::Locked(unlock)) => {
+            println!("{year} {day} unlocks at {unlock}. Skipping.");
+            return;
+        }
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+    match create_day(year, day, layout) {
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+        Ok(t) => println!("{t}"),
+    }
+    if *layout == Layout::Runner {
+        match update_main(year, day) {
+            Ok(_) => println!("Updated aoc{year} main.rs"),
+            Err(e) => println!("{e}"),
+        }
+        return;
+    }
+    if let Err(e) = update_cargo(year, day) {
+        println!("{e}");
+        return;
+    };
+    println!("Updated aoc{year} Cargo.toml");
+    if let Err(e) = update_bacon(year, day) {
+        println!("{e}");
+    };
+    println!("Updated bacon.")
+}
+
+#[derive(Debug, PartialEq)]
+enum InputResult {
+    RequestError,
+    NotLoggedIn,
+    BadText,
+    TooEarly,
+    NotFound,
+    UnknownResponse,
+    BadUrl,
+    /// Not requested as the day unlocks at this time.
+    Locked(DateTime<FixedOffset>),
+}
+
+impl Display for InputResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotLoggedIn => write!(f, "Session cookie missing."),
+            Self::TooEarly => write!(f, "That day is not active yet."),
+            Self::Locked(unlock) => write!(f, "That day unlocks at {unlock}."),
+            Self::BadText => write!(f, "Return text error."),
+            Self::BadUrl | Self::NotFound => write!(f, "Can't find URL. Check dates input."),
+            Self::RequestError => write!(f, "Error sending GET request."),
+            Self::UnknownResponse => write!(f, "Unknown reqwest error. Please try again."),
+        }
+    }
+}
+
+/// Fetches inputs politely: at most one request every `delay`, with an identifying
+/// User-Agent, and never for inputs already on disk or days that haven't unlocked.
+struct Fetcher {
+    client: Client,
+    base: String,
+    delay: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Fetcher {
+    fn new(client: Client, base: String) -> Self {
+        Self {
+            client,
+            base,
+            delay: THROTTLE,
+            last_request: None,
+        }
+    }
+
+    /// Download the input unless `file` already exists, in which case there's nothing to do.
+    fn fetch(
+        &mut self,
+        year: i32,
+        day: u32,
+        file: &Path,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, InputResult> {
+        if file.exists() {
+            return Ok(None);
+        }
+        if let Some(unlock) = unlocks_at(year, day)
+            && now < unlock
+        {
+            return Err(InputResult::Locked(unlock));
+        }
+        self.wait();
+        get_input(&self.client, &self.base, year, day).map(Some)
+    }
+
+    /// Download the puzzle page. Part 2 is only on it once part 1 is solved.
+    fn puzzle(&mut self, year: i32, day: u32, now: DateTime<Utc>) -> Result<String, InputResult> {
+        if let Some(unlock) = unlocks_at(year, day)
+            && now < unlock
+        {
+            return Err(InputResult::Locked(unlock));
+        }
+        self.wait();
+        get_page(&self.client, format!("{}/{year}/day/{day}", self.base))
+    }
+
+    fn wait(&mut self) {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.delay.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+/// Puzzles unlock at midnight US Eastern Standard Time (UTC-5).
+fn unlocks_at(year: i32, day: u32) -> Option<DateTime<FixedOffset>> {
+    let east_coast = FixedOffset::west_opt(5 * 60 * 60)?;
+    east_coast.with_ymd_and_hms(year, 12, day, 0, 0, 0).single()
+}
+
+/// Where each year's downloads are recorded, `aocYYYY/inputs/manifest.toml`:
+/// ```toml
+/// [day04]
+/// downloaded = "2024-12-04T05:00:02+00:00"
+/// ```
+struct Manifest {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl Manifest {
+    fn for_year(year: i32) -> Result<Self, Box<dyn Error>> {
+        Self::load(format!("aoc{year}/inputs/manifest.toml"))
+    }
+
+    fn load<P: Into<PathBuf>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.into();
+        let doc = if path.exists() {
+            fs::read_to_string(&path)?.parse()?
+        } else {
+            DocumentMut::new()
+        };
+        Ok(Self { path, doc })
+    }
+
+    fn record(&mut self, day: u32, when: DateTime<Utc>) {
+        let mut entry = Table::new();
+        entry["downloaded"] = value(when.to_rfc3339());
+        self.doc[&format!("day{day:02}")] = Item::Table(entry);
+    }
+
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, self.doc.to_string())?;
+        Ok(())
+    }
+}
+
+fn get_input<T: Display, U: Display>(
+    client: &Client,
+    base: &str,
+    year: T,
+    day: U,
+) -> Result<String, InputResult> {
+    get_page(client, format!("{base}/{year}/day/{day}/input"))
+}
+
+fn get_page(client: &Client, url: String) -> Result<String, InputResult> {
+    let url = match url.parse::<Url>() {
+        Err(_) => return Err(InputResult::BadUrl),
+        Ok(url) => url,
+    };
+
+    let response = match client.get(url).send() {
+        Err(_) => return Err(InputResult::RequestError),
+        Ok(response) => response,
+    };
+    match (response.status().as_u16(), response.text()) {
+        (_, Err(_)) => Err(InputResult::BadText),
+        (400, Ok(text)) | (404, Ok(text)) => {
+            if text.contains("log in") {
+                Err(InputResult::NotLoggedIn)
+            } else if text.contains("before it unlocks") {
+                Err(InputResult::TooEarly)
+            } else {
+                Err(InputResult::NotFound)
+            }
+        }
+        (200, Ok(text)) => Ok(text),
+        _ => Err(InputResult::UnknownResponse),
+    }
+}
+
+/// `session_cookies` for the session in `ADVENT_OF_CODE_SESSION`.
+fn load_cookies(url: &str) -> Result<CookieStore, String> {
+    let session = env::var(SESSION_VAR).map_err(|_| format!("{SESSION_VAR} is not set in .env"))?;
+    session_cookies(url, session).ok_or(format!("Bad URL {url}"))
+}
+
+/// A store holding the session cookie for the site at `url`. None if the URL is invalid.
+fn session_cookies(url: &str, session: String) -> Option<CookieStore> {
+    let url = url.parse::<Url>().ok()?;
+    // Host only, so it also works for a mock server on a bare IP address.
+    let cookie = RawCookie::build("session", session)
+        .path("/")
+        .secure(url.scheme() == "https")
+        .finish();
+    let mut store = CookieStore::new(None);
+    store.insert_raw(&cookie, &url).ok()?;
+    Some(store)
+}
+
+fn show_preview(data: &str) {
+    let count = data
+        .chars()
+        .filter(|chr| *chr == '\n')
+        .collect::<Vec<_>>()
+        .len();
+    println!("{count} Lines Downloaded");
+    let lines = data
+        .lines()
+        .map(|l| l.chars().take(PREVIEW).collect::<String>())
+        .collect::<Vec<String>>();
+    let max = LINES.min(lines.len());
+    for line in lines.iter().take(max) {
+        println!("{line}");
+    }
+    println!("{} Lines", lines.len())
+}
+
+fn write_file(file: PathBuf, data: String) {
+    let parent = file.parent().unwrap();
+    if file.exists() {
+        return;
+    }
+    let _ = fs::create_dir_all(parent);
+    let _ = fs::write(file, data);
+}
+
+fn get_args(args: &[String]) -> Option<(i32, u32)> {
+    match args.len() {
+        0 => {
+            let east_coast = FixedOffset::west_opt(5 * 60 * 60).unwrap();
+            let today = Utc::now().with_timezone(&east_coast);
+            if today
+                < east_coast
+                    .with_ymd_and_hms(today.year(), 12, 1, 0, 0, 0)
+                    .unwrap()
+            {
+                Some((today.year(), 1))
+            } else {
+                Some((today.year(), today.day()))
+            }
+        }
+        1 => Some((args[0].parse().ok()?, 0)),
+        2 => Some((args[0].parse().ok()?, args[1].parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Create the day's source from `templates/bin.rs` or `templates/runner.rs`.
+fn create_day(year: i32, day: u32, layout: &Layout) -> io::Result<String> {
+    let module = format!("aoc{year}{day:02}");
+    let filename = match layout {
+        Layout::Bins => format!("aoc{year}/src/bin/{module}.rs"),
+        Layout::Runner => format!("aoc{year}/src/{module}.rs"),
+    };
+    let file = Path::new(&filename);
+    if file.exists() {
+        return Ok(format!("{year} {day} already exists."));
+    }
+    if let Some(dir) = file.parent()
+        && !dir.exists()
+    {
+        let _ = create_dir(dir);
+    }
+    let template = format!("{TEMPLATES}/{}.rs", template_name(layout));
+    let template = fs::read_to_string(&template)
+        .map_err(|e| io::Error::new(e.kind(), format!("Could not read {template}. {e}")))?;
+    fs::write(file, fill_template(&template, year, day))?;
+    Ok(format!("Created {year} {day}."))
+}
+
+fn template_name(layout: &Layout) -> &'static str {
+    match layout {
+        Layout::Bins => "bin",
+        Layout::Runner => "runner",
+    }
+}
+
+/// Replace the template variables: `{{year}}`, `{{day}}`, `{{day02}}` (zero padded),
+/// `{{input}}` (the input path) and `{{module}}` (`aocYYYYDD`).
+fn fill_template(template: &str, year: i32, day: u32) -> String {
+    [
+        ("year", year.to_string()),
+        ("day02", format!("{day:02}")),
+        ("day", day.to_string()),
+        ("input", format!("aoc{year}/inputs/day{day:02}.txt")),
+        ("module", format!("aoc{year}{day:02}")),
+    ]
+    .iter()
+    .fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{{{name}}}}}"), value)
+    })
+}
+
+/// The layout asked for with `--layout bin|runner`, otherwise the one the year crate uses.
+/// Years without a crate yet get a binary per day.
+fn get_layout(flag: Option<String>, year: i32) -> Option<Layout> {
+    match flag.as_deref() {
+        Some("bin") => Some(Layout::Bins),
+        Some("runner") => Some(Layout::Runner),
+        Some(_) => None,
+        None => {
+            Some(Year::load(Path::new("."), year as usize).map_or(Layout::Bins, |year| year.layout))
+        }
+    }
+}
+
+/// Add the day's module to the `aoc::days!` list in the year's main.rs.
+fn update_main(year: i32, day: u32) -> Result<(), Box<dyn Error>> {
+    let file = format!("aoc{year}/src/main.rs");
+    let main = get_existing_file(&file)?;
+    let updated = add_day_module(&main, &format!("aoc{year}{day:02}"))
+        .ok_or(format!("No aoc::days! list in {file}"))?;
+    if updated != main {
+        fs::write(file, updated)?;
+    }
+    Ok(())
+}
+
+/// Insert a module into the `aoc::days!(...)` list, keeping it sorted and wrapped the way rustfmt does.
+fn add_day_module(main: &str, module: &str) -> Option<String> {
+    const DAYS: &str = "aoc::days!(";
+    const WIDTH: usize = 100;
+    let start = main.find(DAYS)? + DAYS.len();
+    let end = start + main[start..].find(')')?;
+    let mut modules = main[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    if !modules.contains(&module) {
+        modules.push(module);
+        modules.sort();
+    }
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for module in modules {
+        if !line.is_empty() && 4 + line.len() + module.len() + 2 > WIDTH {
+            lines.push(format!("    {}", line.trim_end()));
+            line.clear();
+        }
+        line.push_str(&format!("{module}, "));
+    }
+    lines.push(format!("    {}", line.trim_end()));
+    Some(format!(
+        "{}\n{}\n{}",
+        &main[..start],
+        lines.join("\n"),
+        &main[end..]
+    ))
+}
+
+/// Update bacon.toml to replace the aoc bin target with the year requested.
+fn update_bacon(year: i32, day: u32) -> io::Result<()> {
+    let mut bacon = get_existing_file("bacon.toml")?
+        .lines()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    for line in bacon.iter_mut() {
+        if line.contains("--package") {
+            *line = format!(r#"    "--package", "aoc{year}","#);
+        }
+        if line.contains("--bin") {
+            *line = format!(r#"    "--bin", "aoc{year}{day:02}","#);
+        }
+    }
+    fs::write("bacon.toml", bacon.join("\n"))
+}
+
+/// Update the year's Cargo.toml file for the new binary.
+fn update_cargo(year: i32, day: u32) -> Result<(), Box<dyn Error>> {
+    let file = format!("aoc{year}/Cargo.toml");
+    let mut cargo = get_existing_file(&file)?.parse::<DocumentMut>()?;
+    let mut new_table = Table::new();
+    new_table["name"] = value(format!("aoc{year}{day:02}"));
+    let bin = cargo
+        .entry("bin")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .unwrap();
+    if !bin.iter().any(|t| *t.to_string() == new_table.to_string()) {
+        bin.push(new_table);
+    };
+    fs::write(file, cargo.to_string())?;
+    Ok(())
+}
+
+/// Create a year's crate using the runner layout, with an empty `aoc::days!` list,
+/// and add it to the workspace members. Anything already in place is left alone.
+fn new_year(root: &Path, year: i32) -> Result<Vec<String>, Box<dyn Error>> {
+    let package = format!("aoc{year}");
+    let dir = root.join(&package);
+    fs::create_dir_all(dir.join("src"))?;
+    let mut steps = Vec::new();
+    for (file, text) in [
+        ("Cargo.toml", year_manifest(&package)),
+        ("src/main.rs", YEAR_MAIN.to_string()),
+    ] {
+        let path = dir.join(file);
+        if path.exists() {
+            steps.push(format!("{package}/{file} already exists."));
+        } else {
+            fs::write(path, text)?;
+            steps.push(format!("Created {package}/{file}."));
+        }
+    }
+
+    let file = root.join("Cargo.toml");
+    let workspace = fs::read_to_string(&file)?;
+    let updated = add_member(&workspace, &package)?;
+    if updated == workspace {
+        steps.push(format!("{package} is already a workspace member."));
+    } else {
+        fs::write(file, updated)?;
+        steps.push(format!("Added {package} to the workspace."));
+    }
+    Ok(steps)
+}
+
+const YEAR_MAIN: &str = "aoc::days!();\n\nfn main() {\n    registry().main();\n}\n";
+
+fn year_manifest(package: &str) -> String {
+    let mut cargo = DocumentMut::new();
+    cargo["package"] = Item::Table(Table::new());
+    cargo["package"]["name"] = value(package);
+    cargo["package"]["version"] = value("0.1.0");
+    cargo["package"]["edition"] = value("2024");
+    cargo["dependencies"] = Item::Table(Table::new());
+    let mut aoc = InlineTable::new();
+    aoc.insert("path", "../aoc".into());
+    cargo["dependencies"]["aoc"] = value(aoc);
+    cargo.to_string()
+}
+
+/// Add a package to the root Cargo.toml's `workspace.members`.
+fn add_member(workspace: &str, package: &str) -> Result<String, Box<dyn Error>> {
+    let mut cargo = workspace.parse::<DocumentMut>()?;
+    let members = cargo["workspace"]
+        .as_table_like_mut()
+        .ok_or("No [workspace] table")?
+        .entry("members")
+        .or_insert(value(Array::new()))
+        .as_array_mut()
+        .ok_or("workspace.members is not an array")?;
+    if !members.iter().any(|m| m.as_str() == Some(package)) {
+        members.push(package);
+    }
+    Ok(cargo.to_string())
+}
+
+/// Write a puzzle's examples to `aocYYYY/examples/dayNN-N.txt` and list them in the
+/// day's tests. Existing example files are kept as they are.
+fn build_examples(year: i32, day: u32, html: &str) {
+    let examples = puzzle::examples(html);
+    if examples.is_empty() {
+        println!("No examples found for {year} {day}.");
+        return;
+    }
+    let mut names = Vec::new();
+    for (idx, example) in examples.iter().enumerate() {
+        let name = (idx + 1).to_string();
+        let path = Example::path(format!("aoc{year}"), day as usize, &name);
+        if path.exists() {
+            println!("{} already exists.", path.display());
+        } else {
+            println!("Created {}:\n{example}", path.display());
+            write_file(path, example.to_string());
+        }
+        names.push(name);
+    }
+    match update_tests(year, day, &names) {
+        Ok(file) if file.contains("/src/bin/") => println!(
+            "{file} tests the examples with aoc::bin_examples!, which needs the parse, part1 and part2 of templates/bin.rs."
+        ),
+        Ok(file) => println!("{file} tests the examples."),
+        Err(e) => println!("{e}"),
+    }
+}
+
+/// List the examples in the day's `aoc::examples!` or `aoc::bin_examples!` call, adding the
+/// call to its tests when there's none.
+fn update_tests(year: i32, day: u32, names: &[String]) -> Result<String, Box<dyn Error>> {
+    let runner = format!("aoc{year}/src/aoc{year}{day:02}.rs");
+    let bin = format!("aoc{year}/src/bin/aoc{year}{day:02}.rs");
+    let (file, call, test) = if Path::new(&runner).exists() {
+        (
+            runner,
+            "aoc::examples!(",
+            format!("aoc::examples!({day}, AocDay {{}});"),
+        )
+    } else if Path::new(&bin).exists() {
+        (
+            bin,
+            "aoc::bin_examples!(",
+            format!("aoc::bin_examples!({day}, {{}});"),
+        )
+    } else {
+        return Err(format!("No source for {year} {day}.").into());
+    };
+    let source = get_existing_file(&file)?;
+    let tested = if source.contains(call) {
+        source.clone()
+    } else {
+        add_test(&source, &test)
+    };
+    let updated = add_examples(&tested, call, names)
+        .ok_or(format!("Could not list the examples in {file}."))?;
+    if updated != source {
+        fs::write(&file, updated)?;
+    }
+    Ok(file)
+}
+
+/// Put a test macro call in the source's `test` or `tests` module, adding a module if it has none.
+fn add_test(source: &str, test: &str) -> String {
+    if source.contains(test) {
+        return source.to_string();
+    }
+    let module = ["mod test {", "mod tests {"]
+        .iter()
+        .filter_map(|module| source.find(module))
+        .min();
+    let Some(start) = module else {
+        let newline = if source.ends_with('\n') { "" } else { "\n" };
+        return format!(
+            "{source}{newline}\n#[cfg(test)]\nmod test {{\n    use super::*;\n\n    {test}\n}}\n"
+        );
+    };
+    let body = start
+        + source[start..]
+            .find('\n')
+            .map_or(source.len() - start, |i| i + 1);
+    let (at, insert) = match source[body..].strip_prefix("    use super::*;\n") {
+        Some(_) => (
+            body + "    use super::*;\n".len(),
+            format!("\n    {test}\n"),
+        ),
+        None => (body, format!("    use super::*;\n\n    {test}\n\n")),
+    };
+    format!("{}{insert}{}", &source[..at], &source[at..])
+}
+
+/// List examples in a macro call like `aoc::examples!(DAY, Solution { test: "name", ... })`,
+/// as `example_NAME: "NAME"`, laid out the way rustfmt lays out a struct literal.
+/// `call` is the start of the macro call. None if there's no such call.
+fn add_examples(source: &str, call: &str, names: &[String]) -> Option<String> {
+    // rustfmt's struct_lit_width.
+    const WIDTH: usize = 18;
+    let start = source.find(call)?;
+    let end = start + source[start..].find(");")?;
+    let open = start + source[start..end].find('{')?;
+    let close = open + source[open..].find('}')?;
+    let mut entries = source[open + 1..close]
+        .split(',')
+        .filter_map(|entry| {
+            let (test, file) = entry.split_once(':')?;
+            Some(format!("{}: {}", test.trim(), file.trim()))
+        })
+        .collect::<Vec<_>>();
+    for name in names {
+        let file = format!("\"{name}\"");
+        if !entries
+            .iter()
+            .any(|entry| entry.ends_with(&format!(": {file}")))
+        {
+            entries.push(format!("example_{name}: {file}"));
+        }
+    }
+
+    let line = entries.join(", ");
+    let body = if entries.is_empty() {
+        "{}".to_string()
+    } else if line.len() <= WIDTH {
+        format!("{{ {line} }}")
+    } else {
+        let indent = source[..start]
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect::<String>();
+        let lines = entries
+            .iter()
+            .map(|entry| format!("\n{indent}    {entry},"))
+            .collect::<String>();
+        format!("{{{lines}\n{indent}}}")
+    };
+    Some(format!("{}{body}{}", &source[..open], &source[close + 1..]))
+}
+
+/// Read the existing file.
+fn get_existing_file(file: &str) -> io::Result<String> {
+    let file = File::open(file)?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = String::new();
+    reader.read_to_string(&mut buffer)?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_server::{Fixture, MockServer, SESSION};
+
+    use super::*;
+
+    fn client(url: &str, session: Option<&str>) -> Client {
+        let store = match session {
+            Some(session) => session_cookies(url, session.into()).unwrap(),
+            None => CookieStore::new(None),
+        };
+        build_client(Arc::new(CookieStoreMutex::new(store)))
+    }
+
+    #[test]
+    fn test_session_cookies() {
+        let store = session_cookies(URL, "abc".into()).unwrap();
+        let actual = store.get("adventofcode.com", "/", "session").unwrap();
+        assert_eq!("abc", actual.value());
+        assert_eq!(Some(true), actual.secure());
+        let store = session_cookies("http://127.0.0.1:8080", "abc".into()).unwrap();
+        assert!(store.get("127.0.0.1", "/", "session").is_some());
+        assert!(session_cookies("not a url", "abc".into()).is_none());
+    }
+
+    #[test]
+    fn test_base_url() {
+        assert_eq!(
+            "http://localhost:1",
+            base_url(Some("http://localhost:1/".into()))
+        );
+        let mut args = ["2015", "--url", "http://x", "4"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(Some("http://x".into()), take_flag(&mut args, "--url"));
+        assert_eq!(Some((2015, 4)), get_args(&args));
+        assert_eq!(None, take_flag(&mut args, "--url"));
+        assert_eq!(None, get_args(&["x".to_string()]));
+    }
+
+    #[test]
+    fn test_no_cookie() {
+        let server = MockServer::start(MockServer::advent());
+        let actual = get_input(&client(&server.url(), None), &server.url(), 2015, 4);
+        assert_eq!(Err(InputResult::NotLoggedIn), actual);
+    }
+
+    #[test]
+    fn test_successful_data() {
+        let server = MockServer::start(MockServer::advent());
+        let client = client(&server.url(), Some(SESSION));
+        let actual = get_input(&client, &server.url(), 2015, 4);
+        assert_eq!(Ok(String::from("bgvyzdsv\n")), actual);
+    }
+
+    #[test]
+    fn test_fetcher() {
+        let server = MockServer::start(MockServer::advent());
+        let mut fetcher = Fetcher::new(client(&server.url(), Some(SESSION)), server.url());
+        fetcher.delay = Duration::from_millis(200);
+        let dir = env::temp_dir().join("build_day_test_fetcher");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("day03.txt");
+        fs::write(&existing, "cached").unwrap();
+        let now = Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(Ok(None), fetcher.fetch(2015, 3, &existing, now));
+        let unlock = unlocks_at(2099, 1).unwrap();
+        assert_eq!(
+            Err(InputResult::Locked(unlock)),
+            fetcher.fetch(2099, 1, &dir.join("day01.txt"), now)
+        );
+        assert!(server.requests().is_empty());
+
+        let start = Instant::now();
+        let first = fetcher.fetch(2015, 4, &dir.join("day04.txt"), now);
+        let second = fetcher.fetch(2015, 5, &dir.join("day05.txt"), now);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(Ok(Some("bgvyzdsv\n".into())), first);
+        assert_eq!(Ok(Some("ugknbfddgicrmopn\n".into())), second);
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(Some(USER_AGENT), requests[0].header("User-Agent"));
+
+        let path = dir.join("manifest.toml");
+        let mut manifest = Manifest::load(&path).unwrap();
+        manifest.record(5, now);
+        manifest.save().unwrap();
+        let manifest = Manifest::load(&path).unwrap();
+        assert_eq!(
+            Some("2016-01-01T00:00:00+00:00"),
+            manifest.doc["day05"]["downloaded"].as_str()
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let template = "({{year}}, {{day}}) day{{day02}} {{input}} {{module}} {{other}}";
+        assert_eq!(
+            "(2024, 5) day05 aoc2024/inputs/day05.txt aoc202405 {{other}}",
+            fill_template(template, 2024, 5)
+        );
+        for name in ["bin", "runner"] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(TEMPLATES)
+                .join(format!("{name}.rs"));
+            let filled = fill_template(&fs::read_to_string(path).unwrap(), 2024, 5);
+            assert!(
+                !filled.contains("{{"),
+                "{name} template has unknown variables"
+            );
+        }
+        assert_eq!(
+            Some(Layout::Runner),
+            get_layout(Some("runner".into()), 2024)
+        );
+        assert_eq!(None, get_layout(Some("lib".into()), 2024));
+    }
+
+    #[test]
+    fn test_add_day_module() {
+        let main = "aoc::days!(\n    aoc202401, aoc202403,\n);\n\nfn main() {\n    registry().main();\n}\n";
+        let updated = add_day_module(main, "aoc202402").unwrap();
+        assert_eq!(
+            "aoc::days!(\n    aoc202401, aoc202402, aoc202403,\n);\n\nfn main() {\n    registry().main();\n}\n",
+            updated
+        );
+        assert_eq!(Some(updated.clone()), add_day_module(&updated, "aoc202402"));
+        assert_eq!(
+            "aoc::days!(\n    aoc202601,\n);",
+            add_day_module("aoc::days!();", "aoc202601").unwrap()
+        );
+        assert_eq!(None, add_day_module("fn main() {}", "aoc202601"));
+
+        let days = (1..=25)
+            .map(|d| format!("aoc2024{d:02}"))
+            .collect::<Vec<_>>();
+        let full = add_day_module("aoc::days!();", &days[24]).unwrap();
+        let full = days[..24]
+            .iter()
+            .fold(full, |main, day| add_day_module(&main, day).unwrap());
+        let existing = include_str!("../../../../aoc2024/src/main.rs");
+        assert!(existing.starts_with(&full));
+    }
+
+    #[test]
+    fn test_new_year() {
+        let root = env::temp_dir().join("build_day_new_year");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nresolver = \"3\"\nmembers = [\"aoc\", \"aoc2025\"]\n",
+        )
+        .unwrap();
+
+        let steps = new_year(&root, 2026).unwrap();
+        assert_eq!(
+            vec![
+                "Created aoc2026/Cargo.toml.",
+                "Created aoc2026/src/main.rs.",
+                "Added aoc2026 to the workspace."
+            ],
+            steps
+        );
+        assert_eq!(
+            "[workspace]\nresolver = \"3\"\nmembers = [\"aoc\", \"aoc2025\", \"aoc2026\"]\n",
+            fs::read_to_string(root.join("Cargo.toml")).unwrap()
+        );
+        let manifest = fs::read_to_string(root.join("aoc2026/Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"aoc2026\""));
+        assert!(manifest.contains("aoc = { path = \"../aoc\" }"));
+        let year = Year::load(&root, 2026).unwrap();
+        assert_eq!((Layout::Runner, vec![]), (year.layout, year.days));
+        let main = fs::read_to_string(root.join("aoc2026/src/main.rs")).unwrap();
+        assert!(add_day_module(&main, "aoc202601").is_some());
+
+        fs::write(root.join("aoc2026/src/main.rs"), "kept").unwrap();
+        let steps = new_year(&root, 2026).unwrap();
+        assert!(steps.iter().all(|step| step.contains("already")));
+        assert_eq!(
+            "kept",
+            fs::read_to_string(root.join("aoc2026/src/main.rs")).unwrap()
+        );
+        assert!(add_member("[package]\nname = \"aoc\"\n", "aoc2026").is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_puzzle() {
+        const PAGE: &str = "<article class=\"day-desc\"><p>For example:</p>\n<pre><code>1\n2\n</code></pre>\n<p>That gives <code><em>3</em></code>.</p></article>";
+        let mut fixtures = MockServer::advent();
+        fixtures.insert("/2015/day/4".into(), Fixture::input(PAGE));
+        let server = MockServer::start(fixtures);
+        let mut fetcher = Fetcher::new(client(&server.url(), Some(SESSION)), server.url());
+        let now = Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap();
+
+        let page = fetcher.puzzle(2015, 4, now).unwrap();
+        assert_eq!(PAGE, page);
+        let examples = puzzle::examples(&page);
+        assert_eq!("part1: 3\n---\n1\n2\n", examples[0].to_string());
+        assert!(matches!(
+            fetcher.puzzle(2099, 1, now),
+            Err(InputResult::Locked(_))
+        ));
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn test_add_examples() {
+        const EXAMPLES: &str = "aoc::examples!(";
+        let names = ["1".to_string(), "2".to_string()];
+        let source =
+            "#[cfg(test)]\nmod test {\n    use super::*;\n\n    aoc::examples!(3, AocDay {});\n}\n";
+        let one = add_examples(source, EXAMPLES, &names[..1]).unwrap();
+        assert!(one.contains("    aoc::examples!(3, AocDay { example_1: \"1\" });\n"));
+        let two = add_examples(&one, EXAMPLES, &names).unwrap();
+        assert!(two.contains(
+            "    aoc::examples!(3, AocDay {\n        example_1: \"1\",\n        example_2: \"2\",\n    });\n"
+        ));
+        assert_eq!(Some(two.clone()), add_examples(&two, EXAMPLES, &names));
+
+        let existing = include_str!("../../../../aoc2024/src/aoc202406.rs");
+        assert_eq!(
+            Some(existing.to_string()),
+            add_examples(existing, EXAMPLES, &names[..1])
+        );
+        assert_eq!(None, add_examples("mod test {}", EXAMPLES, &names));
+        assert_eq!(
+            Some("aoc::bin_examples!(3, { example_1: \"1\" });".into()),
+            add_examples(
+                "aoc::bin_examples!(3, {});",
+                "aoc::bin_examples!(",
+                &names[..1]
+            )
+        );
+    }
+
+    #[test]
+    fn test_add_test() {
+        let test = "aoc::bin_examples!(3, {});";
+        let source = "fn main() {}\n\n#[cfg(test)]\nmod test {\n    use super::*;\n\n    #[test]\n    fn test_example1() {}\n}\n";
+        let added = add_test(source, test);
+        assert_eq!(
+            "fn main() {}\n\n#[cfg(test)]\nmod test {\n    use super::*;\n\n    aoc::bin_examples!(3, {});\n\n    #[test]\n    fn test_example1() {}\n}\n",
+            added
+        );
+        assert_eq!(added, add_test(&added, test));
+        assert_eq!(
+            "fn main() {}\n\n#[cfg(test)]\nmod test {\n    use super::*;\n\n    aoc::bin_examples!(3, {});\n}\n",
+            add_test("fn main() {}", test)
+        );
+        assert_eq!(
+            "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    aoc::bin_examples!(3, {});\n\n    #[test]\n    fn test_part1() {}\n}\n",
+            add_test(
+                "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn test_part1() {}\n}\n",
+                test
+            )
+        );
+
+        // The bin template already has its test.
+        let template = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(TEMPLATES)
+            .join("bin.rs");
+        let template = fill_template(&fs::read_to_string(template).unwrap(), 2025, 3);
+        assert_eq!(template, add_test(&template, test));
+    }
+
+    #[test]
+    fn test_unlocks_at() {
+        let unlock = unlocks_at(2024, 5).unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap(), unlock);
+        assert_eq!(None, unlocks_at(2024, 32));
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let mut fixtures = MockServer::advent();
+        fixtures.insert("/2015/day/6/input".into(), Fixture::status(500, "oops"));
+        fixtures.insert(
+            "/2015/day/7/input".into(),
+            Fixture {
+                truncated: true,
+                ..Fixture::input("123")
+            },
+        );
+        let server = MockServer::start(fixtures);
+        let url = server.url();
+        let client = client(&url, Some(SESSION));
+
+        assert_eq!(
+            Err(InputResult::TooEarly),
+            get_input(&client, &url, 2099, 1)
+        );
+        assert_eq!(
+            Err(InputResult::NotFound),
+            get_input(&client, &url, 2015, 26)
+        );
+        assert_eq!(
+            Err(InputResult::UnknownResponse),
+            get_input(&client, &url, 2015, 6)
+        );
+        assert_eq!(Err(InputResult::BadText), get_input(&client, &url, 2015, 7));
+        assert_eq!(
+            Err(InputResult::BadUrl),
+            get_input(&client, "not a url", 2015, 4)
+        );
+        drop(server);
+        assert_eq!(
+            Err(InputResult::RequestError),
+            get_input(&client, &url, 2015, 4)
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+/// Body of the 400 the site sends for input requests without a session.
+pub const NOT_LOGGED_IN: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+/// Body of the 404 the site sends for days that haven't been released.
+pub const TOO_EARLY: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+/// Session cookie the default fixtures expect.
+pub const SESSION: &str = "mock-session";
+
+/// A canned response for a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub status: u16,
+    pub body: String,
+    /// Answer 400 "log in" instead unless the request has the session cookie.
+    pub needs_session: bool,
+    /// Close the connection before the whole body is sent.
+    pub truncated: bool,
+}
+
+impl Fixture {
+    /// A puzzle input, only given to logged in requests.
+    pub fn input<S: Into<String>>(body: S) -> Self {
+        Self {
+            status: 200,
+            body: body.into(),
+            needs_session: true,
+            truncated: false,
+        }
+    }
+
+    /// A day that hasn't unlocked yet.
+    pub fn locked() -> Self {
+        Self {
+            status: 404,
+            body: TOO_EARLY.into(),
+            needs_session: true,
+            truncated: false,
+        }
+    }
+
+    /// Any other response, sent whether or not there's a session.
+    pub fn status<S: Into<String>>(status: u16, body: S) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            needs_session: false,
+            truncated: false,
+        }
+    }
+}
+
+/// A request the server received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Header names are lower case.
+    pub headers: HashMap<String, String>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    fn has_session(&self) -> bool {
+        self.header("cookie").is_some_and(|cookies| {
+            cookies
+                .split(';')
+                .any(|c| c.trim() == format!("session={SESSION}"))
+        })
+    }
+}
+
+/// A local stand-in for the Advent of Code site, serving fixtures over plain HTTP
+/// on a free port until dropped. Unknown paths get a 404.
+/// ```ignore
+/// let server = MockServer::start(MockServer::advent());
+/// let url = server.url(); // http://127.0.0.1:PORT
+/// ```
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(fixtures: HashMap<String, Fixture>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind mock server");
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let requests = requests.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        handle(stream, &fixtures, &requests);
+                    }
+                }
+            })
+        };
+        Self {
+            addr,
+            requests,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Fixtures for 2015 day 4 (released), 2015 day 5 (released) and 2099 day 1 (locked).
+    pub fn advent() -> HashMap<String, Fixture> {
+        HashMap::from([
+            ("/2015/day/4/input".into(), Fixture::input("bgvyzdsv\n")),
+            (
+                "/2015/day/5/input".into(),
+                Fixture::input("ugknbfddgicrmopn\n"),
+            ),
+            ("/2099/day/1/input".into(), Fixture::locked()),
+        ])
+    }
+
+    /// Base URL without a trailing slash.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle(stream: TcpStream, fixtures: &HashMap<String, Fixture>, requests: &Mutex<Vec<Request>>) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+    let (status, body, truncated) = match fixtures.get(&request.path) {
+        Some(fixture) if fixture.needs_session && !request.has_session() => {
+            (400, NOT_LOGGED_IN.to_string(), false)
+        }
+        Some(fixture) => (fixture.status, fixture.body.clone(), fixture.truncated),
+        None => (404, "404 Not Found\n".to_string(), false),
+    };
+    // Promise more than is sent so the client sees the body end early.
+    let length = body.len() + if truncated { 100 } else { 0 };
+    requests.lock().unwrap().push(request);
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    };
+    let mut stream = stream;
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/plain\r\nContent-Length: {length}\r\nConnection: close\r\n\r\n{body}"
+    );
+    let _ = stream.flush();
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    Some(Request {
+        method,
+        path,
+        headers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn get(server: &MockServer, path: &str, cookie: Option<&str>) -> String {
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        let cookie = cookie.map_or(String::new(), |c| format!("Cookie: {c}\r\n"));
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\n{cookie}\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_mock_server() {
+        let server = MockServer::start(MockServer::advent());
+        let session = format!("other=1; session={SESSION}");
+
+        let response = get(&server, "/2015/day/4/input", Some(&session));
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\nbgvyzdsv\n"));
+        let response = get(&server, "/2015/day/4/input", None);
+        assert!(response.starts_with("HTTP/1.1 400"));
+        assert!(response.contains("log in"));
+        let response = get(&server, "/2099/day/1/input", Some(&session));
+        assert!(response.starts_with("HTTP/1.1 404"));
+        assert!(response.contains("before it unlocks"));
+        assert!(get(&server, "/missing", None).starts_with("HTTP/1.1 404"));
+
+        let requests = server.requests();
+        assert_eq!(4, requests.len());
+        assert_eq!("/2015/day/4/input", requests[0].path);
+        assert_eq!(Some(session.as_str()), requests[0].header("Cookie"));
+    }
+}
@@ -0,0 +1,519 @@
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{self, ExitStatus},
+    time::{Duration, Instant},
+};
+
+use toml_edit::DocumentMut;
+
+/// What the `aoc` binary was asked to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Bench,
+    Verify,
+    List,
+}
+
+/// Parsed `aoc` arguments: `<command> [year] [day] [flags]`.
+/// Flags are passed through to the year binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    pub year: Option<usize>,
+    pub day: Option<usize>,
+    pub flags: Vec<String>,
+}
+
+/// Year binary flags that take a value.
+const VALUE_FLAGS: [&str; 7] = [
+    "--part",
+    "--input",
+    "--warmup",
+    "--iterations",
+    "--json",
+    "--jobs",
+    "--timeout",
+];
+
+impl Cli {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("run") => Command::Run,
+            Some("bench") => Command::Bench,
+            Some("verify") => Command::Verify,
+            Some("list") => Command::List,
+            Some(c) => return Err(format!("Unknown command {c}")),
+            None => return Err("Missing command".into()),
+        };
+        let mut cli = Self {
+            command,
+            year: None,
+            day: None,
+            flags: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") {
+                if VALUE_FLAGS.contains(&arg.as_str()) {
+                    let value = args.next().ok_or_else(|| format!("{arg} needs a value"))?;
+                    cli.flags.extend([arg, value]);
+                } else {
+                    cli.flags.push(arg);
+                }
+                continue;
+            }
+            let number = arg
+                .parse()
+                .map_err(|_| format!("Unexpected argument {arg}"))?;
+            match (cli.year, cli.day) {
+                (None, _) => cli.year = Some(number),
+                (Some(_), None) => cli.day = Some(number),
+                _ => return Err(format!("Unexpected argument {arg}")),
+            }
+        }
+        Ok(cli)
+    }
+}
+
+/// How a year crate runs its days.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    /// `src/main.rs` runs the days through a `runner::Registry`.
+    Runner,
+    /// A `[[bin]]` target per day, named `aocYYYYDD`.
+    Bins,
+}
+
+/// A year crate in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Year {
+    pub year: usize,
+    pub layout: Layout,
+    pub days: Vec<usize>,
+}
+
+impl Year {
+    /// Find the layout and days of `aocYYYY` under the workspace root.
+    pub fn load(root: &Path, year: usize) -> io::Result<Self> {
+        let dir = root.join(package(year));
+        let bins = bin_targets(&dir.join("Cargo.toml"))?;
+        let (layout, names) = if bins.is_empty() {
+            let names = fs::read_dir(dir.join("src"))?
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_suffix(".rs").map(String::from)
+                })
+                .collect();
+            (Layout::Runner, names)
+        } else {
+            (Layout::Bins, bins)
+        };
+        let mut days = names
+            .iter()
+            .filter_map(|name| day_of(year, name))
+            .collect::<Vec<_>>();
+        days.sort();
+        Ok(Self { year, layout, days })
+    }
+
+    /// The `cargo` invocations that carry out the command, one per process.
+    pub fn cargo_args(&self, cli: &Cli) -> Result<Vec<Vec<String>>, String> {
+        let mut base = ["run", "--release", "-q", "-p"].map(String::from).to_vec();
+        base.push(package(self.year));
+        match self.layout {
+            Layout::Runner => {
+                // The runner years also have helper binaries, such as aoc2024all.
+                base.extend(["--bin".into(), package(self.year), "--".into()]);
+                base.extend(cli.day.map(|d| d.to_string()));
+                match cli.command {
+                    Command::Bench => base.push("--bench".into()),
+                    Command::Verify => base.push("--verify".into()),
+                    Command::Run | Command::List => (),
+                }
+                base.extend(cli.flags.iter().cloned());
+                Ok(vec![base])
+            }
+            Layout::Bins => {
+                if cli.command != Command::Run || !cli.flags.is_empty() {
+                    return Err(format!(
+                        "{} uses a binary per day, which only supports `run <year> [day]`",
+                        package(self.year)
+                    ));
+                }
+                let days = match cli.day {
+                    Some(0) => self.days.clone(),
+                    Some(day) if self.days.contains(&day) => vec![day],
+                    Some(day) => return Err(format!("{} has no day {day}", self.year)),
+                    None => self.days.last().copied().into_iter().collect(),
+                };
+                Ok(days
+                    .into_iter()
+                    .map(|day| {
+                        let mut args = base.clone();
+                        args.extend(["--bin".into(), format!("{}{day:02}", package(self.year))]);
+                        args
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Build all of the year's binaries in release mode with one cargo invocation.
+    pub fn build(&self) -> io::Result<ExitStatus> {
+        process::Command::new("cargo")
+            .args(["build", "--release", "-p", &package(self.year), "--bins"])
+            .status()
+    }
+
+    /// The built binaries and their arguments, one run per day: each day's binary, or the runner
+    /// verifying that day.
+    pub fn binaries(&self) -> Vec<(String, Vec<String>)> {
+        match self.layout {
+            Layout::Runner => self
+                .days
+                .iter()
+                .map(|day| (package(self.year), vec![day.to_string(), "--verify".into()]))
+                .collect(),
+            Layout::Bins => self
+                .days
+                .iter()
+                .map(|day| (format!("{}{day:02}", package(self.year)), Vec::new()))
+                .collect(),
+        }
+    }
+}
+
+/// Where cargo puts release builds: `$CARGO_TARGET_DIR/release`, or `target/release` under the root.
+pub fn release_dir(root: &Path) -> PathBuf {
+    let target = env::var_os("CARGO_TARGET_DIR").map_or(root.join("target"), PathBuf::from);
+    target.join("release")
+}
+
+/// A finished run of a built binary.
+#[derive(Debug)]
+pub struct BinRun {
+    pub name: String,
+    /// None if the binary couldn't be started.
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+impl BinRun {
+    pub fn success(&self) -> bool {
+        self.status.is_some_and(|s| s.success())
+    }
+}
+
+/// Run a built binary from `dir`, capturing its output.
+/// The run is named after the binary and its arguments that aren't flags.
+pub fn run_binary(dir: &Path, name: &str, args: &[String]) -> BinRun {
+    let path = dir.join(format!("{name}{}", env::consts::EXE_SUFFIX));
+    let name = std::iter::once(name)
+        .chain(args.iter().map(String::as_str))
+        .filter(|arg| !arg.starts_with("--"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let start = Instant::now();
+    let output = process::Command::new(&path).args(args).output();
+    let duration = start.elapsed();
+    match output {
+        Ok(output) => BinRun {
+            name,
+            status: Some(output.status),
+            stdout: String::from_utf8_lossy(&output.stdout).into(),
+            stderr: String::from_utf8_lossy(&output.stderr).into(),
+            duration,
+        },
+        Err(e) => BinRun {
+            name,
+            status: None,
+            stdout: String::new(),
+            stderr: format!("Could not start {}: {e}", path.display()),
+            duration,
+        },
+    }
+}
+
+/// A table of each run's status and time, then the stderr of every failure.
+pub fn summary(runs: &[BinRun]) -> String {
+    let mut text = format!("\n{:<12} {:<8} {:>8}\n", "Binary", "Status", "Time");
+    for run in runs {
+        let status = match run.status {
+            Some(s) if s.success() => "ok".to_string(),
+            Some(s) => s.code().map_or("killed".into(), |c| format!("exit {c}")),
+            None => "not run".into(),
+        };
+        let ms = run.duration.as_millis();
+        text.push_str(&format!(
+            "{:<12} {status:<8} {:4}.{:03}\n",
+            run.name,
+            ms / 1000,
+            ms % 1000
+        ));
+    }
+    let total = runs
+        .iter()
+        .map(|r| r.duration)
+        .sum::<Duration>()
+        .as_millis();
+    let failed = runs.iter().filter(|r| !r.success()).collect::<Vec<_>>();
+    text.push_str(&format!(
+        "\nTotal: {:3}.{:03}  {} run, {} failed\n",
+        total / 1000,
+        total % 1000,
+        runs.len(),
+        failed.len()
+    ));
+    for run in failed {
+        text.push_str(&format!("\n---- {} stderr ----\n{}", run.name, run.stderr));
+        if !run.stderr.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let layout = match self.layout {
+            Layout::Runner => "runner",
+            Layout::Bins => "bins",
+        };
+        let days = self
+            .days
+            .iter()
+            .map(|d| format!("{d:02}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{} ({layout}): {days}", self.year)
+    }
+}
+
+/// The years listed in the workspace's members, in order.
+pub fn workspace_years(root: &Path) -> io::Result<Vec<usize>> {
+    let doc = read_toml(&root.join("Cargo.toml"))?;
+    let mut years = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str()?.strip_prefix("aoc")?.parse().ok())
+                .collect::<Vec<usize>>()
+        })
+        .unwrap_or_default();
+    years.sort();
+    Ok(years)
+}
+
+/// Names of the `[[bin]]` targets in a manifest.
+pub fn bin_targets(manifest: &Path) -> io::Result<Vec<String>> {
+    let doc = read_toml(manifest)?;
+    Ok(doc
+        .get("bin")
+        .and_then(|b| b.as_array_of_tables())
+        .map(|bins| {
+            bins.iter()
+                .filter_map(|b| b.get("name")?.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn read_toml(path: &Path) -> io::Result<DocumentMut> {
+    fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn package(year: usize) -> String {
+    format!("aoc{year}")
+}
+
+/// The day of a module or binary named `aocYYYYDD`.
+fn day_of(year: usize, name: &str) -> Option<usize> {
+    let day = name.strip_prefix(&package(year))?;
+    match day.len() {
+        2 => day.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cli(args: &[&str]) -> Result<Cli, String> {
+        Cli::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cli() {
+        assert_eq!(
+            Ok(Cli {
+                command: Command::Run,
+                year: Some(2024),
+                day: Some(5),
+                flags: args(&["--part", "2", "--input", "test.txt"]),
+            }),
+            cli(&["run", "2024", "--part", "2", "5", "--input", "test.txt"])
+        );
+        assert_eq!(Ok(Command::List), cli(&["list"]).map(|c| c.command));
+        assert!(cli(&[]).is_err());
+        assert!(cli(&["walk"]).is_err());
+        assert!(cli(&["run", "2024", "1", "2"]).is_err());
+        assert!(cli(&["run", "2024", "--part"]).is_err());
+    }
+
+    #[test]
+    fn test_cargo_args() {
+        let runner = Year {
+            year: 2024,
+            layout: Layout::Runner,
+            days: vec![1, 2],
+        };
+        assert_eq!(
+            Ok(vec![args(&[
+                "run",
+                "--release",
+                "-q",
+                "-p",
+                "aoc2024",
+                "--bin",
+                "aoc2024",
+                "--",
+                "3",
+                "--bench",
+                "--json",
+                "-"
+            ])]),
+            runner.cargo_args(&cli(&["bench", "2024", "3", "--json", "-"]).unwrap())
+        );
+        assert_eq!(
+            Ok(vec![args(&[
+                "run",
+                "--release",
+                "-q",
+                "-p",
+                "aoc2024",
+                "--bin",
+                "aoc2024",
+                "--",
+                "--verify"
+            ])]),
+            runner.cargo_args(&cli(&["verify", "2024"]).unwrap())
+        );
+
+        let bins = Year {
+            year: 2025,
+            layout: Layout::Bins,
+            days: vec![1, 2, 3],
+        };
+        let run = |args: &[&str]| bins.cargo_args(&cli(args).unwrap());
+        assert_eq!(
+            Ok(vec![args(&[
+                "run",
+                "--release",
+                "-q",
+                "-p",
+                "aoc2025",
+                "--bin",
+                "aoc202503"
+            ])]),
+            run(&["run", "2025"])
+        );
+        assert_eq!(Ok(3), run(&["run", "2025", "0"]).map(|a| a.len()));
+        assert!(run(&["run", "2025", "4"]).is_err());
+        assert!(run(&["verify", "2025"]).is_err());
+        assert!(run(&["run", "2025", "--part", "1"]).is_err());
+
+        assert_eq!(
+            vec![
+                ("aoc2024".into(), args(&["1", "--verify"])),
+                ("aoc2024".into(), args(&["2", "--verify"]))
+            ],
+            runner.binaries()
+        );
+        assert_eq!(
+            vec!["aoc202501", "aoc202502", "aoc202503"],
+            bins.binaries()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let runs = [
+            BinRun {
+                name: "aoc202501".into(),
+                status: None,
+                stdout: String::new(),
+                stderr: "Could not start".into(),
+                duration: Duration::from_millis(1500),
+            },
+            run_binary(Path::new("/missing"), "aoc202502", &[]),
+        ];
+        assert!(!runs[1].success());
+        assert!(runs[1].stderr.starts_with("Could not start"));
+        let run = run_binary(Path::new("/missing"), "aoc2024", &args(&["3", "--verify"]));
+        assert_eq!("aoc2024 3", run.name);
+        let text = summary(&runs);
+        assert!(
+            text.starts_with("\nBinary       Status       Time\naoc202501    not run     1.500\n")
+        );
+        assert!(text.contains("2 run, 2 failed"));
+        assert!(text.contains("---- aoc202501 stderr ----\nCould not start\n"));
+    }
+
+    #[test]
+    fn test_load() {
+        let root = std::env::temp_dir().join("aoc_driver_test_load");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc2015/src")).unwrap();
+        fs::create_dir_all(root.join("aoc2016/src/bin")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"aoc\", \"aoc2016\", \"aoc2015\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc2015/Cargo.toml"),
+            "[package]\nname = \"aoc2015\"\n",
+        )
+        .unwrap();
+        for file in ["main.rs", "aoc201502.rs", "aoc201501.rs"] {
+            fs::write(root.join("aoc2015/src").join(file), "").unwrap();
+        }
+        fs::write(
+            root.join("aoc2016/Cargo.toml"),
+            "[package]\nname = \"aoc2016\"\n\n[[bin]]\nname = \"aoc201607\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(vec![2015, 2016], workspace_years(&root).unwrap());
+        let year = Year::load(&root, 2015).unwrap();
+        assert_eq!(
+            (Layout::Runner, vec![1, 2]),
+            (year.layout.clone(), year.days.clone())
+        );
+        assert_eq!("2015 (runner): 01 02", year.to_string());
+        let year = Year::load(&root, 2016).unwrap();
+        assert_eq!((Layout::Bins, vec![7]), (year.layout, year.days));
+        assert!(Year::load(&root, 2017).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,608 @@
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{
+    measure::{CARDINALS, COMPASS, Vec2D},
+    reader::ParseError,
+    search::Graph,
+};
+
+/// A dense, rectangular grid stored row major.
+/// Positions are `Vec2D(row, col)` to match the steps in `CARDINALS` and `COMPASS`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from row major cells. Panics if the cells don't fill the grid.
+    pub fn new(cells: Vec<T>, width: usize) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or_default();
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells can't fill a grid {width} wide",
+            cells.len()
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Build a grid of the given size by calling `f` for each position.
+    pub fn from_fn<F: FnMut(Vec2D<i64>) -> T>(height: usize, width: usize, mut f: F) -> Self {
+        let cells = (0..height * width)
+            .map(|idx| f(Vec2D((idx / width) as i64, (idx % width) as i64)))
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Size of the grid as Vec2D(height, width).
+    pub fn size(&self) -> Vec2D<usize> {
+        Vec2D(self.height, self.width)
+    }
+
+    pub fn contains(&self, pos: Vec2D<i64>) -> bool {
+        (0..self.height as i64).contains(&pos.0) && (0..self.width as i64).contains(&pos.1)
+    }
+
+    fn index_of(&self, pos: Vec2D<i64>) -> Option<usize> {
+        match self.contains(pos) {
+            true => Some(pos.0 as usize * self.width + pos.1 as usize),
+            false => None,
+        }
+    }
+
+    fn position_of(&self, idx: usize) -> Vec2D<i64> {
+        Vec2D((idx / self.width) as i64, (idx % self.width) as i64)
+    }
+
+    pub fn get(&self, pos: Vec2D<i64>) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2D<i64>) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Replace the value at pos, returning the old value if pos is on the grid.
+    pub fn set(&mut self, pos: Vec2D<i64>, value: T) -> Option<T> {
+        self.get_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Iterate over every position and cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D<i64>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.position_of(idx), cell))
+    }
+
+    /// In bounds positions and cells in the N, E, S, W directions.
+    pub fn neighbours(&self, pos: Vec2D<i64>) -> impl Iterator<Item = (Vec2D<i64>, &T)> {
+        self.around(pos, &CARDINALS)
+    }
+
+    /// In bounds positions and cells in all eight compass directions.
+    pub fn neighbours8(&self, pos: Vec2D<i64>) -> impl Iterator<Item = (Vec2D<i64>, &T)> {
+        self.around(pos, &COMPASS)
+    }
+
+    fn around<'a>(
+        &'a self,
+        pos: Vec2D<i64>,
+        steps: &'a [Vec2D<i64>],
+    ) -> impl Iterator<Item = (Vec2D<i64>, &'a T)> {
+        steps
+            .iter()
+            .filter_map(move |step| self.get(pos + *step).map(|cell| (pos + *step, cell)))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Diagonals running down and to the right, starting from the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = (self.height as i64, self.width as i64);
+        (1 - height..width).map(move |offset| {
+            (0..height).filter_map(move |row| self.get(Vec2D(row, row + offset)))
+        })
+    }
+
+    /// Diagonals running down and to the left, starting from the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = (self.height as i64, self.width as i64);
+        (0..height + width - 1)
+            .map(move |sum| (0..height).filter_map(move |row| self.get(Vec2D(row, sum - row))))
+    }
+
+    /// Position of the first cell matching the predicate.
+    pub fn find<F: Fn(&T) -> bool>(&self, predicate: F) -> Option<Vec2D<i64>> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|idx| self.position_of(idx))
+    }
+
+    /// Positions of every cell matching the predicate.
+    pub fn positions<F: Fn(&T) -> bool>(&self, predicate: F) -> impl Iterator<Item = Vec2D<i64>> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(idx, _)| self.position_of(idx))
+    }
+
+    /// Convert every cell to a new type.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Build a grid of the given size filled with value.
+    pub fn filled(height: usize, width: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; height * width],
+            width,
+            height,
+        }
+    }
+
+    /// Swap rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.width, self.height, |pos| {
+            self[Vec2D(pos.1, pos.0)].clone()
+        })
+    }
+
+    /// Rotate 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let last = self.height as i64 - 1;
+        Self::from_fn(self.width, self.height, |pos| {
+            self[Vec2D(last - pos.1, pos.0)].clone()
+        })
+    }
+
+    /// Rotate 90 degrees counter clockwise.
+    pub fn rotate_left(&self) -> Self {
+        let last = self.width as i64 - 1;
+        Self::from_fn(self.width, self.height, |pos| {
+            self[Vec2D(pos.1, last - pos.0)].clone()
+        })
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let last = self.width as i64 - 1;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[Vec2D(pos.0, last - pos.1)].clone()
+        })
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let last = self.height as i64 - 1;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[Vec2D(last - pos.0, pos.1)].clone()
+        })
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell equal to value.
+    pub fn find_value(&self, value: &T) -> Option<Vec2D<i64>> {
+        self.find(|cell| cell == value)
+    }
+}
+
+impl<T> Index<Vec2D<i64>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2D<i64>) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Vec2D<i64>> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2D<i64>) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside the grid"))
+    }
+}
+
+impl<T> Graph for Grid<T> {
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    /// Panics if the rows are not all the same length.
+    fn from(value: Vec<Vec<T>>) -> Self {
+        let width = value.first().map_or(0, |row| row.len());
+        assert!(
+            value.iter().all(|row| row.len() == width),
+            "All rows of a grid must be the same length"
+        );
+        Self::new(value.into_iter().flatten().collect(), width)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T>
+where
+    <T as TryFrom<char>>::Error: Debug,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        for (row, line) in s.lines().enumerate() {
+            let start = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                cells.push(T::try_from(ch).map_err(|e| ParseError {
+                    line: row + 1,
+                    column: col + 1,
+                    token: ch.to_string(),
+                    target: type_name::<T>(),
+                    reason: format!("{e:?}"),
+                })?);
+            }
+            let found = cells.len() - start;
+            match width {
+                None => width = Some(found),
+                Some(w) if w != found => {
+                    return Err(ParseError {
+                        line: row + 1,
+                        column: 1,
+                        token: line.to_string(),
+                        target: type_name::<Self>(),
+                        reason: format!("row is {found} wide, expected {w}"),
+                    });
+                }
+                _ => (),
+            }
+        }
+        Ok(Self::new(cells, width.unwrap_or_default()))
+    }
+}
+
+/// A grid of set cells with every other position holding the background value.
+/// The bounding box grows as cells are set, so the grid can extend in any direction.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2D<i64>, T>,
+    background: T,
+    bounds: Option<(Vec2D<i64>, Vec2D<i64>)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(background: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            background,
+            bounds: None,
+        }
+    }
+
+    /// Value of every position that hasn't been set.
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// Change the value of every unset position, e.g. when an infinite image flips.
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// Value at pos, falling back to the background.
+    pub fn get(&self, pos: Vec2D<i64>) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+
+    /// Whether pos has been explicitly set.
+    pub fn is_set(&self, pos: Vec2D<i64>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Set the value at pos, returning the previously set value.
+    pub fn insert(&mut self, pos: Vec2D<i64>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((min, max)) => (
+                Vec2D(min.0.min(pos.0), min.1.min(pos.1)),
+                Vec2D(max.0.max(pos.0), max.1.max(pos.1)),
+            ),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    /// Return pos to the background, shrinking the bounds if needed.
+    pub fn remove(&mut self, pos: Vec2D<i64>) -> Option<T> {
+        let removed = self.cells.remove(&pos);
+        if let Some((min, max)) = self.bounds
+            && removed.is_some()
+            && (pos.0 == min.0 || pos.0 == max.0 || pos.1 == min.1 || pos.1 == max.1)
+        {
+            self.bounds = self.cells.keys().fold(None, |bounds, p| {
+                Some(match bounds {
+                    None => (*p, *p),
+                    Some((min, max)) => (
+                        Vec2D(min.0.min(p.0), min.1.min(p.1)),
+                        Vec2D(max.0.max(p.0), max.1.max(p.1)),
+                    ),
+                })
+            });
+        }
+        removed
+    }
+
+    /// Inclusive (top left, bottom right) corners of the set cells.
+    pub fn bounds(&self) -> Option<(Vec2D<i64>, Vec2D<i64>)> {
+        self.bounds
+    }
+
+    /// Number of set cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterate over the set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec2D<i64>, &T)> {
+        self.cells.iter()
+    }
+
+    /// Every position inside the bounds grown by margin on each side, row by row.
+    pub fn area(&self, margin: i64) -> impl Iterator<Item = Vec2D<i64>> + use<T> {
+        let (min, max) = self
+            .bounds
+            .map_or((Vec2D(0, 0), Vec2D(-1, -1)), |(min, max)| {
+                (
+                    Vec2D(min.0 - margin, min.1 - margin),
+                    Vec2D(max.0 + margin, max.1 + margin),
+                )
+            });
+        (min.0..=max.0).flat_map(move |row| (min.1..=max.1).map(move |col| Vec2D(row, col)))
+    }
+
+    /// Values at the positions in the N, E, S, W directions.
+    pub fn neighbours(&self, pos: Vec2D<i64>) -> impl Iterator<Item = (Vec2D<i64>, &T)> {
+        CARDINALS
+            .iter()
+            .map(move |step| (pos + *step, self.get(pos + *step)))
+    }
+
+    /// Values at the positions in all eight compass directions.
+    pub fn neighbours8(&self, pos: Vec2D<i64>) -> impl Iterator<Item = (Vec2D<i64>, &T)> {
+        COMPASS
+            .iter()
+            .map(move |step| (pos + *step, self.get(pos + *step)))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Dense copy of the bounded area along with the position of its top left corner.
+    pub fn to_grid(&self) -> (Grid<T>, Vec2D<i64>) {
+        let Some((min, max)) = self.bounds else {
+            return (Grid::new(Vec::new(), 0), Vec2D(0, 0));
+        };
+        let grid = Grid::from_fn(
+            (max.0 - min.0 + 1) as usize,
+            (max.1 - min.1 + 1) as usize,
+            |pos| self.get(pos + min).clone(),
+        );
+        (grid, min)
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// Build from a dense grid, only storing cells that differ from the background.
+    pub fn from_grid(grid: Grid<T>, background: T) -> Self {
+        let width = grid.width;
+        let mut sparse = Self::new(background);
+        for (idx, cell) in grid.cells.into_iter().enumerate() {
+            if cell != sparse.background {
+                sparse.insert(Vec2D((idx / width) as i64, (idx % width) as i64), cell);
+            }
+        }
+        sparse
+    }
+}
+
+impl<T: PartialEq + Default> From<Grid<T>> for SparseGrid<T> {
+    fn from(value: Grid<T>) -> Self {
+        Self::from_grid(value, T::default())
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for row in min.0..=max.0 {
+            if row > min.0 {
+                writeln!(f)?;
+            }
+            for col in min.1..=max.1 {
+                write!(f, "{}", self.get(Vec2D(row, col)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef";
+
+    fn grid() -> Grid<char> {
+        TEXT.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = grid();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&'f'), grid.get(Vec2D(1, 2)));
+        assert_eq!(None, grid.get(Vec2D(2, 0)));
+        assert_eq!(None, grid.get(Vec2D(0, -1)));
+        assert_eq!(TEXT, grid.to_string());
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let actual = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(2, actual.line);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let expected = vec!['b', 'd'];
+        let actual = grid
+            .neighbours(Vec2D(0, 0))
+            .map(|(_, c)| *c)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+        assert_eq!(5, grid.neighbours8(Vec2D(0, 1)).count());
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        let columns = grid
+            .columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["ad", "be", "cf"], columns);
+        let diagonals = grid
+            .diagonals()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["d", "ae", "bf", "c"], diagonals);
+        let anti_diagonals = grid
+            .anti_diagonals()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a", "bd", "ce", "f"], anti_diagonals);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid();
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_right().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_left().to_string());
+        assert_eq!("cba\nfed", grid.flip_horizontal().to_string());
+        assert_eq!("def\nabc", grid.flip_vertical().to_string());
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = grid();
+        assert_eq!(Some(Vec2D(1, 1)), grid.find_value(&'e'));
+        let vowels = grid.positions(|c| "aeiou".contains(*c)).collect::<Vec<_>>();
+        assert_eq!(vec![Vec2D(0, 0), Vec2D(1, 1)], vowels);
+        let numbers = grid.map(|c| *c as u8 - b'a');
+        assert_eq!("012\n345", numbers.to_string());
+    }
+
+    #[test]
+    fn test_sparse_bounds() {
+        let mut sparse = SparseGrid::new('.');
+        sparse.insert(Vec2D(-2, 3), '#');
+        sparse.insert(Vec2D(1, -1), '#');
+        assert_eq!(Some((Vec2D(-2, -1), Vec2D(1, 3))), sparse.bounds());
+        assert_eq!(&'.', sparse.get(Vec2D(100, 100)));
+        assert_eq!("....#\n.....\n.....\n#....", sparse.to_string());
+        sparse.remove(Vec2D(-2, 3));
+        assert_eq!(Some((Vec2D(1, -1), Vec2D(1, -1))), sparse.bounds());
+        assert_eq!(9, sparse.area(1).count());
+    }
+
+    #[test]
+    fn test_sparse_background() {
+        let mut sparse = SparseGrid::new(false);
+        sparse.insert(Vec2D(0, 0), true);
+        sparse.set_background(true);
+        assert_eq!(
+            8,
+            sparse.neighbours8(Vec2D(0, 0)).filter(|(_, v)| **v).count()
+        );
+    }
+
+    #[test]
+    fn test_sparse_dense_round_trip() {
+        let grid = "#..\n..#".parse::<Grid<char>>().unwrap();
+        let sparse = SparseGrid::from_grid(grid.clone(), '.');
+        assert_eq!(2, sparse.len());
+        let (dense, origin) = sparse.to_grid();
+        assert_eq!(Vec2D(0, 0), origin);
+        assert_eq!(grid, dense);
+    }
+}
@@ -1,7 +1,15 @@
+pub mod answers;
+pub mod bench;
+pub mod driver;
+pub mod grid;
 pub mod measure;
+pub mod memory;
+pub mod puzzle;
 pub mod reader;
 pub mod runner;
 pub mod search;
+pub mod testing;
 
+pub use grid::*;
 pub use measure::*;
 pub use reader::*;
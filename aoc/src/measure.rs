@@ -33,22 +33,111 @@ impl FromStr for Dir {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "N" | "U" => Ok(Self::North),
-            "S" | "D" => Ok(Self::South),
-            "E" | "R" => Ok(Self::East),
-            "W" | "L" => Ok(Self::West),
-            d => Err(format!("Unknown direction {d}")),
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(format!("Unknown direction {s}")),
         }
     }
 }
 
+impl TryFrom<char> for Dir {
+    type Error = String;
+
+    /// Accepts compass letters, up/down/left/right letters (either case) and arrows.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Self::North),
+            'v' => Ok(Self::South),
+            '>' => Ok(Self::East),
+            '<' => Ok(Self::West),
+            c => match c.to_ascii_uppercase() {
+                'N' | 'U' => Ok(Self::North),
+                'S' | 'D' => Ok(Self::South),
+                'E' | 'R' => Ok(Self::East),
+                'W' | 'L' => Ok(Self::West),
+                _ => Err(format!("Unknown direction {value}")),
+            },
+        }
+    }
+}
+
+impl TryFrom<Vec2D<i64>> for Dir {
+    type Error = String;
+
+    /// Direction of a vector along one axis, e.g. Vec2D(0, 3) is East.
+    fn try_from(value: Vec2D<i64>) -> Result<Self, Self::Error> {
+        match (value.0.signum(), value.1.signum()) {
+            (-1, 0) => Ok(Self::North),
+            (1, 0) => Ok(Self::South),
+            (0, 1) => Ok(Self::East),
+            (0, -1) => Ok(Self::West),
+            _ => Err(format!("{value} is not along an axis")),
+        }
+    }
+}
+
+impl From<Dir> for char {
+    fn from(value: Dir) -> Self {
+        value.arrow()
+    }
+}
+
 impl Dir {
     pub fn iter() -> impl Iterator<Item = Dir> {
         [Dir::North, Dir::South, Dir::East, Dir::West]
             .iter()
             .copied()
     }
+
+    /// Rotate 90 degrees counter clockwise.
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Dir::North => Dir::West,
+            Dir::West => Dir::South,
+            Dir::South => Dir::East,
+            Dir::East => Dir::North,
+        }
+    }
+
+    /// Rotate 90 degrees clockwise.
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Dir::North => Dir::East,
+            Dir::East => Dir::South,
+            Dir::South => Dir::West,
+            Dir::West => Dir::North,
+        }
+    }
+
+    pub fn turn_around(&self) -> Self {
+        match self {
+            Dir::North => Dir::South,
+            Dir::South => Dir::North,
+            Dir::East => Dir::West,
+            Dir::West => Dir::East,
+        }
+    }
+
+    /// Arrow as drawn in puzzle inputs: ^ v > <
+    pub fn arrow(&self) -> char {
+        match self {
+            Dir::North => '^',
+            Dir::South => 'v',
+            Dir::East => '>',
+            Dir::West => '<',
+        }
+    }
+
+    /// Compass letter: N S E W
+    pub fn letter(&self) -> char {
+        match self {
+            Dir::North => 'N',
+            Dir::South => 'S',
+            Dir::East => 'E',
+            Dir::West => 'W',
+        }
+    }
     pub fn delta<T: Integer + Copy>(&self, point: &Vec2D<T>) -> Vec2D<T> {
         let adder: T = Num::from_str_radix("1", 10).unwrap_or_else(|_| panic!("Can't convert"));
         match self {
@@ -84,6 +173,102 @@ impl Dir {
     }
 }
 
+/// Directions you can move in a grid including diagonals, in `COMPASS` order.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    /// Clockwise from North.
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Self::ALL.iter().copied()
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Rotate 45 degrees counter clockwise.
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Rotate 45 degrees clockwise.
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn turn_around(&self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// Provide with directrion of movement in Point<ROW, COL>
+    pub fn value(&self) -> Vec2D<i64> {
+        COMPASS[self.index()]
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(value: Dir) -> Self {
+        match value {
+            Dir::North => Dir8::North,
+            Dir::South => Dir8::South,
+            Dir::East => Dir8::East,
+            Dir::West => Dir8::West,
+        }
+    }
+}
+
+impl TryFrom<Dir8> for Dir {
+    type Error = String;
+
+    fn try_from(value: Dir8) -> Result<Self, Self::Error> {
+        match value {
+            Dir8::North => Ok(Dir::North),
+            Dir8::South => Ok(Dir::South),
+            Dir8::East => Ok(Dir::East),
+            Dir8::West => Ok(Dir::West),
+            d => Err(format!("{d:?} is not a cardinal direction")),
+        }
+    }
+}
+
+impl TryFrom<Vec2D<i64>> for Dir8 {
+    type Error = String;
+
+    /// Direction of a vector along an axis or a diagonal, e.g. Vec2D(2, -2) is SouthWest.
+    fn try_from(value: Vec2D<i64>) -> Result<Self, Self::Error> {
+        if value.0 != 0 && value.1 != 0 && value.0.abs() != value.1.abs() {
+            return Err(format!("{value} is not along an axis or diagonal"));
+        }
+        let step = Vec2D(value.0.signum(), value.1.signum());
+        COMPASS
+            .iter()
+            .position(|c| *c == step)
+            .map(|idx| Self::ALL[idx])
+            .ok_or_else(|| format!("{value} has no direction"))
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Vec2D<T: Num>(pub T, pub T);
 
@@ -113,14 +298,9 @@ impl From<(usize, usize)> for Vec2D<i64> {
 impl TryFrom<char> for Vec2D<i64> {
     type Error = String;
 
+    /// Unit step for a direction character, see `Dir::try_from`.
     fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'N' | 'U' | '^' => Ok(Self(-1, 0)),
-            'S' | 'D' | 'v' => Ok(Self(1, 0)),
-            'E' | 'R' | '<' => Ok(Self(0, -1)),
-            'W' | 'L' | '>' => Ok(Self(0, 1)),
-            d => Err(format!("Unknown direction {d}")),
-        }
+        Dir::try_from(value).map(|d| d.value())
     }
 }
 
@@ -198,6 +378,146 @@ impl<T: Num + Copy> Vec3D<T> {
     pub fn scale(&self, scale: T) -> Self {
         Self(self.0 * scale, self.1 * scale, self.2 * scale)
     }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self(
+            self.1 * other.2 - self.2 * other.1,
+            self.2 * other.0 - self.0 * other.2,
+            self.0 * other.1 - self.1 * other.0,
+        )
+    }
+
+    /// Square of the straight line distance. Avoids floats when only comparing distances.
+    pub fn euclidean_squared(&self, other: &Self) -> T {
+        let delta = *self - *other;
+        delta.dot(&delta)
+    }
+
+    /// The 6 points sharing a face with this one.
+    pub fn face_neighbours(&self) -> [Self; 6] {
+        let one = T::one();
+        [
+            Self(self.0 - one, self.1, self.2),
+            Self(self.0 + one, self.1, self.2),
+            Self(self.0, self.1 - one, self.2),
+            Self(self.0, self.1 + one, self.2),
+            Self(self.0, self.1, self.2 - one),
+            Self(self.0, self.1, self.2 + one),
+        ]
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one.
+    pub fn cube_neighbours(&self) -> Vec<Self> {
+        let one = T::one();
+        let steps = [T::zero() - one, T::zero(), one];
+        let mut neighbours = Vec::with_capacity(26);
+        for x in steps {
+            for y in steps {
+                for z in steps {
+                    if !(x.is_zero() && y.is_zero() && z.is_zero()) {
+                        neighbours.push(Self(self.0 + x, self.1 + y, self.2 + z));
+                    }
+                }
+            }
+        }
+        neighbours
+    }
+
+    /// Apply one of the 24 proper rotations, indexed 0..24. Rotation 0 is the identity.
+    /// Using the same index for several points rotates them together.
+    pub fn rotate(&self, rotation: usize) -> Self {
+        let (axes, flips) = ROTATIONS[rotation];
+        let coords = [self.0, self.1, self.2];
+        let [x, y, z] = [0, 1, 2].map(|idx| match flips[idx] {
+            true => T::zero() - coords[axes[idx]],
+            false => coords[axes[idx]],
+        });
+        Self(x, y, z)
+    }
+
+    /// Every proper rotation of this point in the same order as `rotate`.
+    pub fn rotations(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..ROTATIONS.len()).map(|r| self.rotate(r))
+    }
+}
+
+impl<T: Num + Copy + Signed> Vec3D<T> {
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs(self.0 - other.0) + abs(self.1 - other.1) + abs(self.2 - other.2)
+    }
+}
+
+/// Axis permutation and sign flips for each rotation that keeps a right handed frame.
+const ROTATIONS: [([usize; 3], [bool; 3]); 24] = rotation_table();
+
+const fn rotation_table() -> [([usize; 3], [bool; 3]); 24] {
+    // (permutation, is odd)
+    let perms = [
+        ([0, 1, 2], false),
+        ([1, 2, 0], false),
+        ([2, 0, 1], false),
+        ([0, 2, 1], true),
+        ([1, 0, 2], true),
+        ([2, 1, 0], true),
+    ];
+    let mut table = [([0, 1, 2], [false; 3]); 24];
+    let mut idx = 0;
+    let mut p = 0;
+    while p < perms.len() {
+        let mut signs = 0;
+        while signs < 8 {
+            let flips = [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0];
+            let odd_flips = (flips[0] as u8 + flips[1] as u8 + flips[2] as u8) % 2 == 1;
+            // Determinant is +1 when the permutation parity matches the flip parity.
+            if odd_flips == perms[p].1 {
+                table[idx] = (perms[p].0, flips);
+                idx += 1;
+            }
+            signs += 1;
+        }
+        p += 1;
+    }
+    table
+}
+
+impl<T: Num> From<[T; 3]> for Vec3D<T> {
+    fn from(value: [T; 3]) -> Self {
+        let [x, y, z] = value;
+        Self(x, y, z)
+    }
+}
+
+impl<T: Num> From<Vec3D<T>> for [T; 3] {
+    fn from(value: Vec3D<T>) -> Self {
+        [value.0, value.1, value.2]
+    }
+}
+
+impl<T: Num + FromStr> FromStr for Vec3D<T> {
+    type Err = String;
+
+    /// Parse "x,y,z", allowing spaces around the values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<T>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("Could not parse {s} as a point"))?;
+        match <[T; 3]>::try_from(values) {
+            Ok(v) => Ok(v.into()),
+            Err(_) => Err(format!("{s} does not have 3 coordinates")),
+        }
+    }
+}
+
+impl<T: Num + Display> Display for Vec3D<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
 }
 
 impl<T: Num + Copy> FromIterator<T> for Vec3D<T> {
@@ -248,3 +568,368 @@ impl<T: Num + Copy + std::ops::SubAssign> std::ops::SubAssign for Vec3D<T> {
         self.2 -= rhs.2;
     }
 }
+
+/// A point with any number of dimensions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct VecN<T: Num, const N: usize>(pub [T; N]);
+
+impl<T: Num + Copy, const N: usize> VecN<T, N> {
+    /// Scales a point by some value
+    pub fn scale(&self, scale: T) -> Self {
+        Self(self.0.map(|v| v * scale))
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::zero(), |acc, (a, b)| acc + *a * *b)
+    }
+
+    /// Square of the straight line distance. Avoids floats when only comparing distances.
+    pub fn euclidean_squared(&self, other: &Self) -> T {
+        let delta = *self - *other;
+        delta.dot(&delta)
+    }
+
+    /// The 2 * N points one step away along a single axis.
+    pub fn face_neighbours(&self) -> Vec<Self> {
+        let mut neighbours = Vec::with_capacity(2 * N);
+        for axis in 0..N {
+            let mut below = *self;
+            below.0[axis] = below.0[axis] - T::one();
+            let mut above = *self;
+            above.0[axis] = above.0[axis] + T::one();
+            neighbours.push(below);
+            neighbours.push(above);
+        }
+        neighbours
+    }
+
+    /// The 3^N - 1 points touching this one, including diagonals.
+    pub fn neighbours(&self) -> Vec<Self> {
+        let steps = [T::zero() - T::one(), T::zero(), T::one()];
+        let count = 3_usize.pow(N as u32);
+        (0..count)
+            .filter(|&idx| idx != count / 2)
+            .map(|mut idx| {
+                let mut point = *self;
+                for v in point.0.iter_mut() {
+                    *v = *v + steps[idx % 3];
+                    idx /= 3;
+                }
+                point
+            })
+            .collect()
+    }
+}
+
+impl<T: Num + Copy + Signed, const N: usize> VecN<T, N> {
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::zero(), |acc, (a, b)| acc + abs(*a - *b))
+    }
+}
+
+impl<T: Num + Copy, const N: usize> Default for VecN<T, N> {
+    fn default() -> Self {
+        Self([T::zero(); N])
+    }
+}
+
+impl<T: Num, const N: usize> From<[T; N]> for VecN<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Num> From<Vec2D<T>> for VecN<T, 2> {
+    fn from(value: Vec2D<T>) -> Self {
+        Self([value.0, value.1])
+    }
+}
+
+impl<T: Num> From<VecN<T, 2>> for Vec2D<T> {
+    fn from(value: VecN<T, 2>) -> Self {
+        let [a, b] = value.0;
+        Self(a, b)
+    }
+}
+
+impl<T: Num> From<Vec3D<T>> for VecN<T, 3> {
+    fn from(value: Vec3D<T>) -> Self {
+        Self([value.0, value.1, value.2])
+    }
+}
+
+impl<T: Num> From<VecN<T, 3>> for Vec3D<T> {
+    fn from(value: VecN<T, 3>) -> Self {
+        value.0.into()
+    }
+}
+
+impl<T: Num + Copy, const N: usize> FromIterator<T> for VecN<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let v = iter.into_iter().collect::<Vec<_>>();
+        match <[T; N]>::try_from(v) {
+            Ok(v) => Self(v),
+            Err(_) => panic!("Can only collect length {N} iterators into points."),
+        }
+    }
+}
+
+impl<T: Num + FromStr, const N: usize> FromStr for VecN<T, N> {
+    type Err = String;
+
+    /// Parse comma separated values, allowing spaces around the values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<T>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("Could not parse {s} as a point"))?;
+        match <[T; N]>::try_from(values) {
+            Ok(v) => Ok(Self(v)),
+            Err(_) => Err(format!("{s} does not have {N} coordinates")),
+        }
+    }
+}
+
+impl<T: Num, const N: usize> std::ops::Index<usize> for VecN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T: Num, const N: usize> std::ops::IndexMut<usize> for VecN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Num + Copy, const N: usize> std::ops::Add for VecN<T, N> {
+    type Output = VecN<T, N>;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a = *a + b;
+        }
+        self
+    }
+}
+
+impl<T: Num + Copy, const N: usize> std::ops::Sub for VecN<T, N> {
+    type Output = VecN<T, N>;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a = *a - b;
+        }
+        self
+    }
+}
+
+impl<T: Num + Copy + std::ops::AddAssign, const N: usize> std::ops::AddAssign for VecN<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: Num + Copy + std::ops::SubAssign, const N: usize> std::ops::SubAssign for VecN<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T: Num + Display, const N: usize> Display for VecN<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (idx, v) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{v}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_vec3d_products() {
+        let x = Vec3D(1, 0, 0);
+        let y = Vec3D(0, 1, 0);
+        assert_eq!(Vec3D(0, 0, 1), x.cross(&y));
+        assert_eq!(0, x.dot(&y));
+        assert_eq!(32, Vec3D(1, 2, 3).dot(&Vec3D(4, 5, 6)));
+        assert_eq!(5, Vec3D(1, 2, 3).manhattan(&Vec3D(-1, 0, 4)));
+        assert_eq!(9, Vec3D(1, 2, 3).euclidean_squared(&Vec3D(-1, 0, 4)));
+    }
+
+    #[test]
+    fn test_vec3d_neighbours() {
+        let point = Vec3D(1, 1, 1);
+        assert!(point
+            .face_neighbours()
+            .iter()
+            .all(|n| n.manhattan(&point) == 1));
+        let cube = point.cube_neighbours();
+        assert_eq!(26, cube.len());
+        assert!(!cube.contains(&point));
+        assert!(cube.contains(&Vec3D(0, 2, 0)));
+    }
+
+    #[test]
+    fn test_vec3d_conversions() {
+        assert_eq!(Ok(Vec3D(1, -2, 30)), "1,-2, 30".parse());
+        assert!("1,2".parse::<Vec3D<i64>>().is_err());
+        assert!("1,2,a".parse::<Vec3D<i64>>().is_err());
+        assert_eq!(Vec3D(1, 2, 3), [1, 2, 3].into());
+        assert_eq!("(1, 2, 3)", Vec3D(1, 2, 3).to_string());
+    }
+
+    #[test]
+    fn test_vec3d_rotations() {
+        let point = Vec3D(1, 2, 3);
+        assert_eq!(point, point.rotate(0));
+        let rotations = point.rotations().collect::<Vec<_>>();
+        assert_eq!(24, rotations.iter().collect::<HashSet<_>>().len());
+        let (x, y, z) = (Vec3D(1, 0, 0), Vec3D(0, 1, 0), Vec3D(0, 0, 1));
+        for r in 0..24 {
+            assert_eq!(z.rotate(r), x.rotate(r).cross(&y.rotate(r)));
+            assert_eq!(14, point.rotate(r).dot(&point.rotate(r)));
+        }
+    }
+
+    #[test]
+    fn test_dir_matches_cardinals() {
+        let cardinals = [Dir::North, Dir::East, Dir::South, Dir::West];
+        for (dir, step) in cardinals.iter().zip(CARDINALS) {
+            assert_eq!(step, dir.value());
+            assert_eq!(step, dir.scale(1));
+            assert_eq!(step, dir.delta(&Vec2D(0, 0)));
+            assert_eq!(Ok(*dir), Dir::try_from(step));
+            assert_eq!(Ok(*dir), Dir::try_from(dir.scale(5)));
+            assert_eq!(step, Dir8::from(*dir).value());
+        }
+        assert_eq!(4, Dir::iter().count());
+    }
+
+    #[test]
+    fn test_dir8_matches_compass() {
+        for (dir, step) in Dir8::iter().zip(COMPASS) {
+            assert_eq!(step, dir.value());
+            assert_eq!(Ok(dir), Dir8::try_from(step));
+            assert_eq!(Ok(dir), Dir8::try_from(Vec2D(step.0 * 3, step.1 * 3)));
+        }
+        for (dir, step) in Dir8::iter().skip(1).step_by(2).zip(ORDINALS) {
+            assert_eq!(step, dir.value());
+        }
+        assert!(Dir8::try_from(Vec2D(1, 2)).is_err());
+        assert!(Dir8::try_from(Vec2D(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_turns() {
+        for dir in Dir::iter() {
+            assert_eq!(dir, dir.turn_left().turn_right());
+            assert_eq!(dir.turn_around(), dir.turn_right().turn_right());
+            assert_eq!(dir.turn_around(), dir.turn_left().turn_left());
+            assert_eq!(dir.turn_around().value(), Vec2D(0, 0) - dir.value());
+            // Turning right is a clockwise rotation of (row, col): (r, c) -> (c, -r).
+            let Vec2D(r, c) = dir.value::<i64>();
+            assert_eq!(Vec2D(c, -r), dir.turn_right().value());
+            assert_eq!(
+                Dir8::from(dir.turn_right()),
+                Dir8::from(dir).turn_right().turn_right()
+            );
+        }
+        for dir in Dir8::iter() {
+            assert_eq!(dir, dir.turn_left().turn_right());
+            assert_eq!(dir.turn_around().value(), Vec2D(0, 0) - dir.value());
+        }
+    }
+
+    #[test]
+    fn test_char_conversions_agree() {
+        for dir in Dir::iter() {
+            assert_eq!(Ok(dir), Dir::try_from(dir.arrow()));
+            assert_eq!(Ok(dir), Dir::try_from(dir.letter()));
+            assert_eq!(Ok(dir), dir.letter().to_string().parse());
+            assert_eq!(Ok(dir.value()), Vec2D::try_from(dir.arrow()));
+            assert_eq!(dir.arrow(), char::from(dir));
+        }
+        for (chars, dir) in [
+            ("NnUu^", Dir::North),
+            ("SsDdv", Dir::South),
+            ("EeRr>", Dir::East),
+            ("WwLl<", Dir::West),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Ok(dir), Dir::try_from(c));
+            }
+        }
+        for c in (0..=u32::from(char::MAX)).filter_map(char::from_u32) {
+            assert_eq!(
+                Dir::try_from(c).map(|d| d.value()),
+                Vec2D::<i64>::try_from(c),
+                "{c:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_vecn_arithmetic() {
+        let a = VecN([1, 2, 3, 4]);
+        let b = VecN([4, 3, 2, 1]);
+        assert_eq!(VecN([5, 5, 5, 5]), a + b);
+        assert_eq!(VecN([-3, -1, 1, 3]), a - b);
+        assert_eq!(VecN([2, 4, 6, 8]), a.scale(2));
+        assert_eq!(20, a.dot(&b));
+        assert_eq!(8, a.manhattan(&b));
+        assert_eq!(20, a.euclidean_squared(&b));
+        assert_eq!("(1, 2, 3, 4)", a.to_string());
+        assert_eq!(Ok(a), "1, 2,3,4".parse());
+        assert!("1,2,3".parse::<VecN<i64, 4>>().is_err());
+    }
+
+    #[test]
+    fn test_vecn_neighbours() {
+        let point = VecN([0, 0, 0, 0]);
+        let neighbours = point.neighbours();
+        assert_eq!(80, neighbours.len());
+        assert_eq!(80, neighbours.iter().collect::<HashSet<_>>().len());
+        assert!(!neighbours.contains(&point));
+        assert_eq!(8, point.face_neighbours().len());
+    }
+
+    #[test]
+    fn test_vecn_conversions() {
+        assert_eq!(VecN([1, 2]), Vec2D(1, 2).into());
+        assert_eq!(Vec2D(1, 2), VecN([1, 2]).into());
+        assert_eq!(VecN([1, 2, 3]), Vec3D(1, 2, 3).into());
+        assert_eq!(Vec3D(1, 2, 3), VecN([1, 2, 3]).into());
+        let mut cube = Vec3D(1, 1, 1).cube_neighbours();
+        let mut generic = VecN::from(Vec3D(1, 1, 1))
+            .neighbours()
+            .into_iter()
+            .map(Vec3D::from)
+            .collect::<Vec<_>>();
+        cube.sort();
+        generic.sort();
+        assert_eq!(cube, generic);
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+};
+
+/// Allocator that counts the current thread's allocations on top of the system allocator.
+/// Installed as the global allocator by the `alloc-stats` feature, for example
+/// `cargo run --release -p aoc2024 --features aoc/alloc-stats`.
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Counters are per thread so days run in parallel don't see each other's allocations.
+/// Memory freed on a different thread than it was allocated on can push `current` below zero.
+struct Counters {
+    current: Cell<isize>,
+    peak: Cell<isize>,
+    count: Cell<usize>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            current: Cell::new(0),
+            peak: Cell::new(0),
+            count: Cell::new(0),
+        }
+    };
+}
+
+fn allocated(size: usize) {
+    // Fails while the thread is being torn down, when there's nothing left to report.
+    let _ = COUNTERS.try_with(|c| {
+        let current = c.current.get() + size as isize;
+        c.current.set(current);
+        c.peak.set(c.peak.get().max(current));
+        c.count.set(c.count.get() + 1);
+    });
+}
+
+fn freed(size: usize) {
+    let _ = COUNTERS.try_with(|c| c.current.set(c.current.get() - size as isize));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// Heap use of a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Allocations and reallocations made.
+    pub allocations: usize,
+    /// Most bytes held at once above what the thread held when the phase started.
+    pub peak: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocs",
+            format_bytes(self.peak),
+            self.allocations
+        )
+    }
+}
+
+/// Measures the current thread's heap use from `start` until `finish`.
+pub struct Tracker {
+    current: isize,
+    count: usize,
+}
+
+impl Tracker {
+    pub fn start() -> Self {
+        COUNTERS.with(|c| {
+            c.peak.set(c.current.get());
+            Self {
+                current: c.current.get(),
+                count: c.count.get(),
+            }
+        })
+    }
+
+    /// None unless the counting allocator is installed.
+    pub fn finish(self) -> Option<AllocStats> {
+        cfg!(feature = "alloc-stats").then(|| self.stats())
+    }
+
+    fn stats(&self) -> AllocStats {
+        COUNTERS.with(|c| AllocStats {
+            allocations: c.count.get() - self.count,
+            peak: (c.peak.get() - self.current).max(0) as usize,
+        })
+    }
+}
+
+/// Bytes in the largest binary unit that keeps the value at least 1.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker() {
+        let small = Layout::from_size_align(1000, 8).unwrap();
+        let large = Layout::from_size_align(4000, 8).unwrap();
+        let tracker = Tracker::start();
+        unsafe {
+            let a = Counting.alloc(small);
+            let b = Counting.realloc(a, small, 4000);
+            Counting.dealloc(b, large);
+            let c = Counting.alloc_zeroed(small);
+            Counting.dealloc(c, small);
+        }
+        assert_eq!(
+            AllocStats {
+                allocations: 3,
+                peak: 4000
+            },
+            tracker.stats()
+        );
+        assert_eq!(cfg!(feature = "alloc-stats"), tracker.finish().is_some());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 MiB", format_bytes(3 * 1024 * 1024));
+        assert_eq!(
+            "peak 2.0 KiB, 7 allocs",
+            AllocStats {
+                allocations: 7,
+                peak: 2048
+            }
+            .to_string()
+        );
+    }
+}
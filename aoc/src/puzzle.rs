@@ -0,0 +1,203 @@
+use crate::testing::Example;
+
+/// One part of a puzzle page, an `<article class="day-desc">`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    /// The text of each `<pre><code>` block.
+    pub blocks: Vec<String>,
+    /// The last emphasised code in the description, usually the example's answer.
+    pub answer: Option<String>,
+    /// The text before each block, to tell a new example from a diagram of an old one.
+    intros: Vec<String>,
+}
+
+/// The parts of a saved puzzle page. Part 2 only shows once part 1 is solved.
+pub fn parts(html: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        parts.push(part(&article[..end]));
+        rest = &article[end..];
+    }
+    parts
+}
+
+fn part(article: &str) -> Part {
+    let mut blocks = Vec::new();
+    let mut intros = Vec::new();
+    let mut prose = String::new();
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre><code>") {
+        let code = &rest[start + "<pre><code>".len()..];
+        let end = code.find("</code></pre>").unwrap_or(code.len());
+        intros.push(text(&rest[..start]));
+        blocks.push(text(&code[..end]));
+        prose.push_str(&rest[..start]);
+        rest = code.get(end + "</code></pre>".len()..).unwrap_or("");
+    }
+    prose.push_str(rest);
+
+    // Answers are written as <code><em>41</em></code> or <em><code>41</code></em>.
+    let answer = ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| prose.rfind(open).map(|start| (start, open.len())))
+        .max()
+        .and_then(|(start, len)| {
+            let answer = &prose[start + len..];
+            let end = answer.find("</")?;
+            Some(text(&answer[..end]))
+        });
+    Part {
+        blocks,
+        answer,
+        intros,
+    }
+}
+
+/// The examples on a puzzle page with their expected answers, found by rule of thumb:
+/// the first block of part 1 is the example for both parts, unless part 2 introduces a
+/// block of its own as an example. The results are worth checking by eye.
+pub fn examples(html: &str) -> Vec<Example> {
+    let parts = parts(html);
+    let mut examples = Vec::<Example>::new();
+    if let Some(part1) = parts.first()
+        && let Some(input) = part1.blocks.first()
+    {
+        examples.push(Example {
+            part1: part1.answer.clone(),
+            part2: None,
+            input: input.clone(),
+        });
+    }
+    let Some(part2) = parts.get(1) else {
+        return examples;
+    };
+    let seen = |block: &String| parts[0].blocks.contains(block);
+    let new = part2
+        .blocks
+        .iter()
+        .zip(&part2.intros)
+        .find(|(block, intro)| !seen(block) && introduces_example(intro));
+    match new {
+        Some((input, _)) => examples.push(Example {
+            part1: None,
+            part2: part2.answer.clone(),
+            input: input.clone(),
+        }),
+        None => {
+            if let Some(example) = examples.first_mut() {
+                example.part2 = part2.answer.clone();
+            }
+        }
+    }
+    examples
+}
+
+/// Whether the last sentence before a block presents it as an example.
+fn introduces_example(intro: &str) -> bool {
+    let intro = intro.trim_end();
+    let sentence = intro
+        .trim_end_matches(':')
+        .rsplit(['.', '!', '?'])
+        .next()
+        .unwrap_or(intro)
+        .to_lowercase();
+    intro.ends_with(':') && sentence.contains("example") && !sentence.contains("above")
+}
+
+/// Strip the tags from some HTML and decode its entities.
+fn text(html: &str) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for chr in html.chars() {
+        match chr {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            chr if !in_tag => stripped.push(chr),
+            _ => (),
+        }
+    }
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&apos;", "'"),
+        ("&nbsp;", " "),
+        ("&amp;", "&"),
+    ]
+    .iter()
+    .fold(stripped, |text, (entity, chr)| text.replace(entity, chr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Sums ---</h2>
+<p>Add up the numbers. For example:</p>
+<pre><code>1
+2
+<em>3</em>
+</code></pre>
+<p>The lines add up to <code><em>6</em></code>. Some have <code>&lt;&gt;</code> in them.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Multiply them instead. In the example above, that gives:</p>
+<pre><code>1 * 2 * 3
+</code></pre>
+<p>The product is <em><code>6</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parts() {
+        let parts = parts(PAGE);
+        assert_eq!(2, parts.len());
+        assert_eq!(vec!["1\n2\n3\n".to_string()], parts[0].blocks);
+        assert_eq!(Some("6".into()), parts[0].answer);
+        assert_eq!(Some("6".into()), parts[1].answer);
+        assert_eq!(
+            "a < b & \"c\"",
+            text("a &lt; <em>b</em> &amp; &quot;c&quot;")
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            vec![Example {
+                part1: Some("6".into()),
+                part2: Some("6".into()),
+                input: "1\n2\n3\n".into(),
+            }],
+            examples(PAGE)
+        );
+
+        let page = PAGE
+            .replace(
+                "In the example above, that gives:",
+                "Here is a new example:",
+            )
+            .replace("<em><code>6</code></em>", "<em><code>5</code></em>");
+        let examples = examples(&page);
+        assert_eq!(2, examples.len());
+        assert_eq!(None, examples[0].part2);
+        assert_eq!(
+            Example {
+                part1: None,
+                part2: Some("5".into()),
+                input: "1 * 2 * 3\n".into(),
+            },
+            examples[1]
+        );
+
+        let part1 = &PAGE[..PAGE.find("<p>Your").unwrap()];
+        assert_eq!(1, super::examples(part1).len());
+        assert!(super::examples("<p>No puzzle</p>").is_empty());
+    }
+}
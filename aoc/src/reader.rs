@@ -1,25 +1,231 @@
 use std::{
+    any::type_name,
+    error::Error,
     fmt::{Debug, Display},
     fs::read_to_string,
-    path::Path,
+    io::{self, Read, stdin},
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
+use crate::grid::Grid;
+
+/// Where the puzzle text comes from.
+///
+/// Plain strings convert with [`Input::from`]: a single line containing a path
+/// separator or ending in `.txt` is a [`Input::File`], anything else is a
+/// [`Input::Literal`]. Use [`Input::literal`] for text that only looks like a path. Use [`Input::infer`] for the old "file if it exists"
+/// behaviour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Literal(String),
+    Stdin,
+    Embedded(&'static str),
+}
+
+impl Input {
+    pub fn file<P: Into<PathBuf>>(path: P) -> Self {
+        Self::File(path.into())
+    }
+
+    pub fn literal<S: Into<String>>(text: S) -> Self {
+        Self::Literal(text.into())
+    }
+
+    /// Treat the text as a file if that path exists, otherwise as the puzzle text itself.
+    pub fn infer<S: AsRef<str>>(text: S) -> Self {
+        let text = text.as_ref();
+        match Path::new(text).exists() {
+            true => Self::File(text.into()),
+            false => Self::Literal(text.into()),
+        }
+    }
+
+    /// Read the full text of the input.
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Self::File(path) => read_to_string(path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => InputError::NotFound(path.clone()),
+                _ => InputError::Io(path.display().to_string(), e),
+            }),
+            Self::Literal(text) => Ok(text.clone()),
+            Self::Stdin => {
+                let mut text = String::new();
+                stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|e| InputError::Io("stdin".into(), e))?;
+                Ok(text)
+            }
+            Self::Embedded(text) => Ok(text.to_string()),
+        }
+    }
+}
+
+fn looks_like_path(text: &str) -> bool {
+    !text.contains('\n') && (text.contains(['/', '\\']) || text.ends_with(".txt"))
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        match looks_like_path(value) {
+            true => Self::File(value.into()),
+            false => Self::Literal(value.into()),
+        }
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        match looks_like_path(&value) {
+            true => Self::File(value.into()),
+            false => Self::Literal(value),
+        }
+    }
+}
+
+impl From<&String> for Input {
+    fn from(value: &String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<&Path> for Input {
+    fn from(value: &Path) -> Self {
+        Self::File(value.into())
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(value: PathBuf) -> Self {
+        Self::File(value)
+    }
+}
+
+impl From<&Input> for Input {
+    fn from(value: &Input) -> Self {
+        value.clone()
+    }
+}
+
+/// The input could not be read.
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Io(String, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(f, "input file {} does not exist", path.display()),
+            Self::Io(source, e) => write!(f, "could not read {source}: {e}"),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Failure from one of the `try_*` readers.
+#[derive(Debug)]
+pub enum ReadError {
+    Input(InputError),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input(e) => write!(f, "{e}"),
+            Self::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+impl From<InputError> for ReadError {
+    fn from(value: InputError) -> Self {
+        Self::Input(value)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(value: ParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// A token in the input that could not be parsed.
+/// Line and column are 1 based and point to the start of the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub target: &'static str,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Build the error for `token`, which must be a slice of `text`.
+    fn new<U: FromStr>(text: &str, token: &str, err: U::Err) -> Self
+    where
+        <U as FromStr>::Err: Debug,
+    {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        Self {
+            line,
+            column,
+            token: token.to_string(),
+            target: type_name::<U>(),
+            reason: format!("{err:?}"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: could not parse `{}` as {} ({})",
+            self.line, self.column, self.token, self.target, self.reason
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse a token that is a slice of `text`, reporting its location on failure.
+fn parse_token<U: FromStr>(text: &str, token: &str) -> Result<U, ReadError>
+where
+    <U as FromStr>::Err: Debug,
+{
+    token
+        .parse::<U>()
+        .map_err(|e| ReadError::Parse(ParseError::new::<U>(text, token, e)))
+}
+
 /// Read the text of a file to a vec of strings
-pub fn read_lines<T: AsRef<Path> + Display>(path: T) -> Vec<String> {
-    contents(path)
+pub fn read_lines<T: Into<Input>>(input: T) -> Vec<String> {
+    contents(input)
         .lines()
         .filter(|s| !s.is_empty())
         .map(|s| s.to_string())
         .collect()
 }
 
-/// Gather a string of text or file name to a string
-pub fn contents<T: AsRef<Path> + Display>(path: T) -> String {
-    match path.as_ref().exists() {
-        false => path.to_string(),
-        true => read_to_string(path).expect("Failed to open file {path}"),
-    }
+/// Gather the text of the input to a string
+pub fn contents<T: Into<Input>>(input: T) -> String {
+    try_contents(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Gather the text of the input to a string, failing if it can't be read.
+pub fn try_contents<T: Into<Input>>(input: T) -> Result<String, InputError> {
+    input.into().read()
 }
 /// Reads records that are line delineated.
 /// For example:
@@ -28,25 +234,35 @@ pub fn contents<T: AsRef<Path> + Display>(path: T) -> String {
 ///
 /// 3423
 /// 2543
-pub fn read_number_records<T: AsRef<Path> + Display, U: FromStr>(path: T) -> Vec<Vec<U>>
+pub fn read_number_records<T: Into<Input>, U: FromStr>(input: T) -> Vec<Vec<U>>
 where
     <U as FromStr>::Err: Debug,
 {
-    contents(path)
-        .split("\n\n")
+    try_read_number_records(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads records that are line delineated, returning the first number that fails to parse.
+pub fn try_read_number_records<T: Into<Input>, U: FromStr>(
+    input: T,
+) -> Result<Vec<Vec<U>>, ReadError>
+where
+    <U as FromStr>::Err: Debug,
+{
+    let text = try_contents(input)?;
+    text.split("\n\n")
         .filter(|s| !s.is_empty())
         .map(|s| {
             s.lines()
                 .filter(|s| !s.is_empty())
-                .map(|num| num.parse::<U>().expect("Unable to parse number"))
-                .collect::<Vec<U>>()
+                .map(|num| parse_token(&text, num))
+                .collect::<Result<Vec<U>, _>>()
         })
         .collect()
 }
 
 /// Return records split by \n\n.
-pub fn read_string_records<T: AsRef<Path> + Display>(path: T) -> Vec<String> {
-    contents(path)
+pub fn read_string_records<T: Into<Input>>(input: T) -> Vec<String> {
+    contents(input)
         .split("\n\n")
         .filter(|s| !s.is_empty())
         .map(|s| s.to_string())
@@ -54,67 +270,112 @@ pub fn read_string_records<T: AsRef<Path> + Display>(path: T) -> Vec<String> {
 }
 
 /// Reads the text of a file to a vector of numbers.
-pub fn read_numbers<T: AsRef<Path> + Display, U: FromStr>(path: T) -> Vec<U>
+pub fn read_numbers<T: Into<Input>, U: FromStr>(input: T) -> Vec<U>
 where
     <U as FromStr>::Err: Debug,
 {
-    read_lines(path)
-        .iter()
-        .map(|l| l.parse::<U>().expect("Could not parse number {l:?}"))
+    try_read_numbers(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads the text of a file to a vector of numbers, returning the first line that fails to parse.
+pub fn try_read_numbers<T: Into<Input>, U: FromStr>(input: T) -> Result<Vec<U>, ReadError>
+where
+    <U as FromStr>::Err: Debug,
+{
+    let text = try_contents(input)?;
+    text.lines()
+        .filter(|s| !s.is_empty())
+        .map(|l| parse_token(&text, l))
         .collect()
 }
 
 /// Reads the text of a file to a vector of vector of numbers.
-pub fn read_number_lists<T: AsRef<Path> + Display, U: FromStr>(path: T, sep: &str) -> Vec<Vec<U>>
+pub fn read_number_lists<T: Into<Input>, U: FromStr>(input: T, sep: &str) -> Vec<Vec<U>>
+where
+    <U as FromStr>::Err: Debug,
+{
+    try_read_number_lists(input, sep).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads the text of a file to a vector of vector of numbers, returning the first value that fails to parse.
+pub fn try_read_number_lists<T: Into<Input>, U: FromStr>(
+    input: T,
+    sep: &str,
+) -> Result<Vec<Vec<U>>, ReadError>
 where
     <U as FromStr>::Err: Debug,
 {
-    read_lines(path)
-        .iter()
+    let text = try_contents(input)?;
+    text.lines()
+        .filter(|s| !s.is_empty())
         .map(|l| {
             l.split(sep)
-                .map(|l| l.parse::<U>().expect("Could not parse number {l:?}"))
-                .collect()
+                .map(|v| parse_token(&text, v))
+                .collect::<Result<Vec<U>, _>>()
         })
         .collect()
 }
 
 /// Reads the file to a list of chars.
-pub fn read_line<T: AsRef<Path> + Display>(path: T) -> Vec<char> {
-    contents(path).chars().filter(|&chr| chr != '\n').collect()
+pub fn read_line<T: Into<Input>>(input: T) -> Vec<char> {
+    contents(input).chars().filter(|&chr| chr != '\n').collect()
 }
 
-/// Reads the file to a list of chars.
-pub fn read_line_record<T: AsRef<Path> + Display, U: FromStr>(path: T) -> Vec<U>
+/// Reads a single comma separated record.
+pub fn read_line_record<T: Into<Input>, U: FromStr>(input: T) -> Vec<U>
+where
+    <U as FromStr>::Err: Debug,
+{
+    try_read_line_record(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a single comma separated record, returning the first value that fails to parse.
+pub fn try_read_line_record<T: Into<Input>, U: FromStr>(input: T) -> Result<Vec<U>, ReadError>
 where
     <U as FromStr>::Err: Debug,
 {
-    contents(path)
-        .trim()
+    let text = try_contents(input)?;
+    text.trim()
         .split(",")
-        .map(|v| v.parse().expect("Could not parse number {v:?}"))
+        .map(|v| parse_token(&text, v))
         .collect()
 }
 
 /// Reads the file to a grid (vec of vec) of chars
-pub fn read_grid<T: AsRef<Path> + Display>(path: T) -> Vec<Vec<char>> {
-    contents(path)
+pub fn read_grid<T: Into<Input>>(input: T) -> Vec<Vec<char>> {
+    contents(input)
         .lines()
         .map(|l| l.chars().collect())
         .collect()
 }
 
 /// Reads the file to a grid (vec of vec) of u8
-pub fn read_grid_numbers<T: AsRef<Path> + Display>(path: T) -> Vec<Vec<u8>> {
-    contents(path)
+pub fn read_grid_numbers<T: Into<Input>>(input: T) -> Vec<Vec<u8>> {
+    contents(input)
         .lines()
         .map(|l| l.chars().map(|c| c as u8 - b'0').collect())
         .collect()
 }
 
+/// Reads the file to a Grid of cells built from each char.
+pub fn read_grid_as<T: Into<Input>, U: TryFrom<char>>(input: T) -> Grid<U>
+where
+    <U as TryFrom<char>>::Error: Debug,
+{
+    try_read_grid_as(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads the file to a Grid of cells built from each char, returning the first cell that fails.
+pub fn try_read_grid_as<T: Into<Input>, U: TryFrom<char>>(input: T) -> Result<Grid<U>, ReadError>
+where
+    <U as TryFrom<char>>::Error: Debug,
+{
+    Ok(try_contents(input)?.parse()?)
+}
+
 /// Reads the contents to an iterator of coordinates / char pairs
-pub fn read_grid_to_map<T: AsRef<Path> + Display>(path: T) -> Vec<((usize, usize), char)> {
-    contents(path)
+pub fn read_grid_to_map<T: Into<Input>>(input: T) -> Vec<((usize, usize), char)> {
+    contents(input)
         .lines()
         .enumerate()
         .flat_map(|(row, line)| {
@@ -146,9 +407,114 @@ pub fn read_grid_to_map<T: AsRef<Path> + Display>(path: T) -> Vec<((usize, usize
 /// let actual = aoc::read_grid_records(input);
 /// assert_eq!(expected, actual);
 /// ```
-pub fn read_grid_records<T: AsRef<Path> + Display>(path: T) -> Vec<Vec<Vec<char>>> {
-    contents(path)
+pub fn read_grid_records<T: Into<Input>>(input: T) -> Vec<Vec<Vec<char>>> {
+    contents(input)
         .split("\n\n")
         .map(|l| l.lines().map(|r| r.chars().collect()).collect())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error<T: Debug>(result: Result<T, ReadError>) -> ParseError {
+        match result {
+            Err(ReadError::Parse(e)) => e,
+            r => panic!("Expected a parse error, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_input_from_str() {
+        assert_eq!(
+            Input::File("aoc2024/inputs/day01.txt".into()),
+            Input::from("aoc2024/inputs/day01.txt")
+        );
+        assert_eq!(Input::File("day01.txt".into()), Input::from("day01.txt"));
+        assert_eq!(
+            Input::Literal("2333133121414131402".into()),
+            Input::from("2333133121414131402")
+        );
+        assert_eq!(
+            Input::Literal("a/b\nc/d".into()),
+            Input::from(String::from("a/b\nc/d"))
+        );
+        assert_eq!(
+            Input::File("aoc2024/examples/day06".into()),
+            Input::from("aoc2024/examples/day06")
+        );
+        assert!(matches!(
+            try_contents("aoc1999/inputs/day01"),
+            Err(InputError::NotFound(_))
+        ));
+        assert_eq!("3/4 \\o/", contents(Input::literal("3/4 \\o/")));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let actual = try_contents("aoc1999/inputs/day01.txt");
+        assert!(matches!(actual, Err(InputError::NotFound(_))));
+        let actual = try_read_numbers::<_, i64>("aoc1999/inputs/day01.txt");
+        assert!(matches!(actual, Err(ReadError::Input(_))));
+    }
+
+    #[test]
+    fn test_infer() {
+        assert_eq!(
+            Input::Literal("aoc1999/inputs/day01.txt".into()),
+            Input::infer("aoc1999/inputs/day01.txt")
+        );
+        assert_eq!(Input::File("Cargo.toml".into()), Input::infer("Cargo.toml"));
+    }
+
+    #[test]
+    fn test_embedded() {
+        let expected = vec!["a", "b"];
+        let actual = read_lines(Input::Embedded("a\n\nb\n"));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_read_numbers() {
+        let expected = vec![1, 2, 3];
+        let actual = try_read_numbers::<_, i64>("1\n2\n\n3\n").unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_read_numbers_error() {
+        let actual = parse_error(try_read_numbers::<_, i64>("1\n\n12a\n4"));
+        assert_eq!((3, 1), (actual.line, actual.column));
+        assert_eq!("12a", actual.token);
+        assert_eq!("i64", actual.target);
+        assert!(
+            actual
+                .to_string()
+                .starts_with("line 3, column 1: could not parse `12a` as i64")
+        );
+    }
+
+    #[test]
+    fn test_try_read_number_lists_error() {
+        let actual = parse_error(try_read_number_lists::<_, u8>("1 2 3\n4 x5 6", " "));
+        assert_eq!((2, 3), (actual.line, actual.column));
+        assert_eq!("x5", actual.token);
+    }
+
+    #[test]
+    fn test_try_read_line_record_error() {
+        let actual = parse_error(try_read_line_record::<_, usize>("3,4,-3,1,2\n"));
+        assert_eq!((1, 5), (actual.line, actual.column));
+        assert_eq!("-3", actual.token);
+    }
+
+    #[test]
+    fn test_try_read_number_records() {
+        let expected = vec![vec![1000, 2000], vec![3000]];
+        let actual = try_read_number_records::<_, u32>("1000\n2000\n\n3000\n").unwrap();
+        assert_eq!(expected, actual);
+        let actual = parse_error(try_read_number_records::<_, u32>("1000\n2000\n\n30o0\n"));
+        assert_eq!((4, 1), (actual.line, actual.column));
+    }
+}
@@ -1,8 +1,29 @@
 use std::{
+    any::Any,
+    collections::BTreeMap,
+    convert::Infallible,
+    env,
     fmt::Display,
+    fs,
+    io::{self, Write},
+    panic::{AssertUnwindSafe, catch_unwind},
+    path::PathBuf,
+    process::exit,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
     time::{Duration, Instant},
 };
 
+use crate::{
+    answers::{Answers, Check},
+    bench::{BenchOptions, bench_day, print_bench, to_json},
+    memory::{AllocStats, Tracker},
+};
+
 pub trait Runner {
     fn name(&self) -> (usize, usize);
     fn parse(&mut self);
@@ -10,42 +31,1168 @@ pub trait Runner {
     fn part2(&mut self) -> String;
 }
 
+/// Error returned by a `Solution` phase.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A day whose phases can fail. Every `Runner` is a `Solution`.
+pub trait Solution {
+    fn name(&self) -> (usize, usize);
+    fn parse(&mut self) -> Result<(), Error>;
+    fn part1(&mut self) -> Result<Answer, Error>;
+    fn part2(&mut self) -> Result<Answer, Error>;
+}
+
+impl<T: Runner + ?Sized> Solution for T {
+    fn name(&self) -> (usize, usize) {
+        Runner::name(self)
+    }
+
+    fn parse(&mut self) -> Result<(), Error> {
+        Runner::parse(self);
+        Ok(())
+    }
+
+    fn part1(&mut self) -> Result<Answer, Error> {
+        Ok(Runner::part1(self).parse()?)
+    }
+
+    fn part2(&mut self) -> Result<Answer, Error> {
+        Ok(Runner::part2(self).parse()?)
+    }
+}
+
+/// A puzzle answer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    /// Multi line output such as letters drawn in # and .
+    Art(String),
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{v}"),
+            Answer::UInt(v) => write!(f, "{v}"),
+            Answer::Text(v) | Answer::Art(v) => write!(f, "{v}"),
+            Answer::Unsolved => write!(f, "Unsolved"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Classify the text output of a `Runner`.
+    /// Numbers only count when they print back the same, so "0123" or "+5" stay text.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "Unsolved" || s.is_empty() {
+            Ok(Answer::Unsolved)
+        } else if let Some(v) = s.parse::<u64>().ok().filter(|v| v.to_string() == s) {
+            Ok(Answer::UInt(v))
+        } else if let Some(v) = s.parse::<i64>().ok().filter(|v| v.to_string() == s) {
+            Ok(Answer::Int(v))
+        } else {
+            Ok(s.into())
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        value.to_string().into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        match value.trim_matches('\n').contains('\n') {
+            true => Answer::Art(value),
+            false => Answer::Text(value),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::$variant(value as $target)
+            }
+        })*
+    };
+}
+
+answer_from!(Int, i64, i8, i16, i32, i64, isize);
+answer_from!(UInt, u64, u8, u16, u32, u64, usize);
+
+/// Why a phase produced no result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Error(String),
+    Panicked(String),
+    /// Still running when the timeout ran out.
+    Timeout(Duration),
+    /// An earlier phase failed.
+    Skipped,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "FAILED {e}"),
+            Failure::Panicked(e) => write!(f, "PANICKED {e}"),
+            Failure::Timeout(t) => write!(f, "TIMEOUT after {:.3}s", t.as_secs_f64()),
+            Failure::Skipped => write!(f, "Skipped"),
+        }
+    }
+}
+
+/// Result of a phase and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed<T> {
+    pub result: Result<T, Failure>,
+    pub duration: Duration,
+    /// Heap use, when built with the `alloc-stats` feature.
+    pub memory: Option<AllocStats>,
+}
+
+impl<T> Timed<T> {
+    fn skipped() -> Self {
+        Self {
+            result: Err(Failure::Skipped),
+            duration: Duration::ZERO,
+            memory: None,
+        }
+    }
+}
+
+/// Everything that happened while running a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub year: usize,
+    pub day: usize,
+    pub parse: Timed<()>,
+    pub part1: Timed<Answer>,
+    pub part2: Timed<Answer>,
+    pub check1: Check,
+    pub check2: Check,
+}
+
+impl Report {
+    /// Time spent in the day's phases.
+    pub fn duration(&self) -> Duration {
+        self.parse.duration + self.part1.duration + self.part2.duration
+    }
+
+    /// Whether a phase returned an error, panicked or timed out.
+    pub fn failed(&self) -> bool {
+        [
+            self.parse.result.as_ref().err(),
+            self.part1.result.as_ref().err(),
+            self.part2.result.as_ref().err(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|f| *f != Failure::Skipped)
+    }
+}
+
+/// Builds a day's solution from the path or text of its input.
+pub type Builder = fn(String) -> Box<dyn Solution>;
+
+/// A registered day that can build its solution on demand.
+pub struct Day {
+    pub year: usize,
+    pub day: usize,
+    builder: Builder,
+}
+
+impl Day {
+    /// Where `build_day` saves the day's input, relative to the workspace root.
+    pub fn default_input(&self) -> String {
+        format!("aoc{}/inputs/day{:02}.txt", self.year, self.day)
+    }
+
+    pub fn build<S: Into<String>>(&self, input: S) -> Box<dyn Solution> {
+        (self.builder)(input.into())
+    }
+
+    /// Build the solution using the default input.
+    pub fn load(&self) -> Box<dyn Solution> {
+        self.build(self.default_input())
+    }
+}
+
+/// The days of a year keyed by `Runner::name()`. Usually built with `aoc::days!`.
+#[derive(Default)]
+pub struct Registry {
+    days: Vec<Day>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a day. The year and day come from the built solution's `name()`.
+    pub fn register(&mut self, builder: Builder) {
+        let (year, day) = builder(String::new()).name();
+        match self.days.binary_search_by_key(&day, |d| d.day) {
+            Ok(_) => panic!("{year} day {day} registered twice"),
+            Err(idx) => self.days.insert(idx, Day { year, day, builder }),
+        }
+    }
+
+    pub fn get(&self, day: usize) -> Option<&Day> {
+        self.days.iter().find(|d| d.day == day)
+    }
+
+    /// The most recent day.
+    pub fn last(&self) -> Option<&Day> {
+        self.days.last()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Day> {
+        self.days.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    /// Days picked by the options. No day runs the last one, unless verifying.
+    fn selected(&self, options: &Options) -> Vec<&Day> {
+        match options.day {
+            Some(0) => self.days.iter().collect(),
+            None if options.verify => self.days.iter().collect(),
+            Some(d) => self.get(d).or(self.last()).into_iter().collect(),
+            None => self.last().into_iter().collect(),
+        }
+    }
+
+    /// Run the selected days, checking each part against the year's stored answers.
+    pub fn run(&self, options: &Options) -> Vec<Report> {
+        let days = self.selected(options);
+        let Some(year) = days.first().map(|d| d.year) else {
+            println!("No days registered.");
+            return Vec::new();
+        };
+        // Stored answers are for the default inputs.
+        let mut answers = match options.input {
+            Some(_) => Answers::default(),
+            None => Answers::for_year(year).unwrap_or_else(|e| {
+                println!("Could not load answers for {year}: {e}");
+                Answers::default()
+            }),
+        };
+
+        let start = Instant::now();
+        let reports = match options.jobs {
+            Some(jobs) if days.len() > 1 => run_parallel(&days, options, &answers, jobs),
+            _ => days
+                .iter()
+                .map(|day| run_day(day, options, &answers, &mut io::stdout()))
+                .collect(),
+        };
+        if days.len() > 1 {
+            println!("\nTotal: {}", format_total(start.elapsed()));
+            if options.jobs.is_some() {
+                let cpu = reports.iter().map(Report::duration).sum();
+                println!("CPU:   {}", format_total(cpu));
+            }
+        }
+
+        if options.record && options.input.is_none() {
+            let mut recorded = 0;
+            for report in &reports {
+                for (part, timed) in [(1, &report.part1), (2, &report.part2)] {
+                    if let Ok(answer) = &timed.result
+                        && answers.record(report.day, part, answer)
+                    {
+                        recorded += 1;
+                    }
+                }
+            }
+            match answers.save() {
+                Ok(_) => println!("Recorded {recorded} new answers."),
+                Err(e) => println!("Could not save answers: {e}"),
+            }
+        }
+        reports
+    }
+
+    /// Benchmark the selected days, writing the results as JSON if asked to.
+    pub fn bench(&self, options: &Options) {
+        let bench_options = options.bench.unwrap_or_default();
+        // Keep stdout to the JSON alone when that's where it goes.
+        let mut out: Box<dyn Write> = match options.json.as_deref() {
+            Some(path) if path.as_os_str() == "-" => Box::new(io::stderr()),
+            _ => Box::new(io::stdout()),
+        };
+        let benches = self
+            .selected(options)
+            .into_iter()
+            .map(|day| {
+                let input = options.input.clone().unwrap_or_else(|| day.default_input());
+                let bench = bench_day(day, &input, &bench_options);
+                print_bench(&bench, &mut out);
+                bench
+            })
+            .collect::<Vec<_>>();
+
+        let json = to_json(&bench_options, &benches);
+        match options.json.as_deref() {
+            Some(path) if path.as_os_str() == "-" => print!("{json}"),
+            Some(path) => {
+                if let Err(e) = fs::write(path, json) {
+                    println!("Could not write {}: {e}", path.display());
+                }
+            }
+            None => (),
+        }
+    }
+
+    /// Entry point for a year binary.
+    /// `[day] [--part N] [--input PATH] [--verify] [--record] [--parallel] [--jobs N] [--timeout SECS]`
+    /// `[--bench] [--warmup N] [--iterations N] [--json PATH]`:
+    /// no day runs the last day, 0 runs every day and any other number runs that day.
+    /// `--verify` exits non-zero if any answer is wrong or any phase fails. `--json -` writes the
+    /// JSON to stdout and the table to stderr.
+    pub fn main(&self) {
+        let options = match Options::parse(env::args().skip(1)) {
+            Ok(options) => options,
+            Err(e) => {
+                println!("{e}");
+                exit(2);
+            }
+        };
+        if options.bench.is_some() {
+            self.bench(&options);
+            return;
+        }
+        let reports = self.run(&options);
+        if options.verify {
+            let checks = reports
+                .iter()
+                .flat_map(|r| [&r.check1, &r.check2])
+                .collect::<Vec<_>>();
+            let count = |f: fn(&Check) -> bool| checks.iter().filter(|c| f(c)).count();
+            let failed = count(|c| matches!(c, Check::Fail(_)));
+            let errors = reports.iter().filter(|r| r.failed()).count();
+            println!(
+                "\nVerify: {} passed, {failed} failed, {} new, {errors} errors",
+                count(|c| *c == Check::Pass),
+                count(|c| *c == Check::New)
+            );
+            if failed + errors > 0 {
+                exit(1);
+            }
+        }
+    }
+}
+
+/// Command line options shared by the year binaries.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Day to run. None runs the last day and 0 runs every day.
+    pub day: Option<usize>,
+    /// Run every day unless one is given and exit non-zero on any wrong answer.
+    pub verify: bool,
+    /// Store answers for parts that don't have one yet.
+    pub record: bool,
+    /// Only show this part. Part 1 still runs before part 2.
+    pub part: Option<usize>,
+    /// Path or text to use instead of the day's default input.
+    pub input: Option<String>,
+    /// Benchmark instead of running once.
+    pub bench: Option<BenchOptions>,
+    /// Where to write benchmark results.
+    pub json: Option<PathBuf>,
+    /// Run days on this many threads. 0 uses every core.
+    pub jobs: Option<usize>,
+    /// Give up on a phase that takes longer than this.
+    pub timeout: Option<Duration>,
+}
+
+impl Options {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| format!("{name} needs a value"));
+            let mut count = |name: &str| {
+                let v = value(name)?;
+                v.parse::<usize>()
+                    .map_err(|_| format!("{name} needs a number, not {v}"))
+            };
+            match arg.as_str() {
+                "--verify" => options.verify = true,
+                "--record" => options.record = true,
+                "--bench" => {
+                    options.bench.get_or_insert_default();
+                }
+                "--warmup" => options.bench.get_or_insert_default().warmup = count(&arg)?,
+                "--iterations" => options.bench.get_or_insert_default().iterations = count(&arg)?,
+                "--json" => options.json = Some(value(&arg)?.into()),
+                "--part" => match count(&arg)? {
+                    part @ (1 | 2) => options.part = Some(part),
+                    part => return Err(format!("There is no part {part}")),
+                },
+                "--input" => options.input = Some(value(&arg)?),
+                "--jobs" => options.jobs = Some(count(&arg)?),
+                "--parallel" => options.jobs = Some(0),
+                "--timeout" => {
+                    let v = value(&arg)?;
+                    let seconds = v
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|s| *s > 0.0)
+                        .ok_or_else(|| format!("{arg} needs a number of seconds, not {v}"))?;
+                    let timeout = Duration::try_from_secs_f64(seconds)
+                        .map_err(|e| format!("{arg} {v} is not a usable timeout: {e}"))?;
+                    options.timeout = Some(timeout);
+                }
+                a => match a.parse() {
+                    Ok(day) if options.day.is_none() => options.day = Some(day),
+                    _ => return Err(format!("Unexpected argument {a}")),
+                },
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Declare a year's day modules and build their `Registry`.
+/// Each module must provide `AocDay::new(input)` implementing `Runner` or `Solution`.
+/// ```ignore
+/// aoc::days!(aoc202401, aoc202402);
+///
+/// fn main() {
+///     registry().main();
+/// }
+/// ```
+#[macro_export]
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        $(mod $day;)*
+
+        fn registry() -> $crate::runner::Registry {
+            let mut registry = $crate::runner::Registry::new();
+            $(registry.register(|input| Box::new($day::AocDay::new(input)));)*
+            registry
+        }
+    };
+}
+
 pub fn output<T: Display>(output: T) -> String {
     format!("{}", output)
 }
 
-pub fn run_solution<T: Runner + ?Sized>(solution: &mut T) {
+/// Run each phase of a day, printing the answers and timings as they finish.
+/// Errors and panics are reported against the phase instead of ending the run.
+/// Answers are checked against `aocYYYY/answers.toml` when it exists.
+pub fn run_solution<T: Solution + ?Sized>(solution: &mut T) -> Report {
+    let (year, _) = solution.name();
+    let answers = Answers::for_year(year).unwrap_or_default();
+    run_checked(solution, &answers, None)
+}
+
+/// `run_solution` using already loaded answers, optionally showing only one part.
+pub fn run_checked<T: Solution + ?Sized>(
+    solution: &mut T,
+    answers: &Answers,
+    part: Option<usize>,
+) -> Report {
+    run_to(solution, answers, part, &mut io::stdout())
+}
+
+/// `run_checked` writing its output to `out`.
+pub fn run_to<T: Solution + ?Sized>(
+    solution: &mut T,
+    answers: &Answers,
+    part: Option<usize>,
+    out: &mut dyn Write,
+) -> Report {
     let (year, day) = solution.name();
-    println!("---- {year}: {day:02} ----");
+    run_phases(year, day, &mut Local(solution), answers, part, out)
+}
 
-    let start = Instant::now();
-    solution.parse();
-    let end = start.elapsed().as_millis();
-    println!("{:3}.{:03} Parsing", end / 1000, end % 1000);
+/// Where a day's phases run.
+trait Phases {
+    fn parse(&mut self) -> Timed<()>;
+    /// Run the next part that has to run.
+    fn part(&mut self, part: usize) -> Timed<Answer>;
+}
 
-    let start = Instant::now();
-    let part1 = solution.part1();
-    let end = start.elapsed();
-    print_solution(1, &part1, end);
+/// Phases run on the current thread.
+struct Local<'a, T: ?Sized>(&'a mut T);
+
+impl<T: Solution + ?Sized> Phases for Local<'_, T> {
+    fn parse(&mut self) -> Timed<()> {
+        timed(|| self.0.parse())
+    }
+
+    fn part(&mut self, part: usize) -> Timed<Answer> {
+        match part {
+            1 => timed(|| self.0.part1()),
+            _ => timed(|| self.0.part2()),
+        }
+    }
+}
+
+enum Event {
+    Parse(Timed<()>),
+    Part(Timed<Answer>),
+}
+
+/// Phases run on a thread of their own, giving up on any that takes longer than the timeout.
+/// A phase that times out is abandoned, its thread is left running until the program exits.
+struct Isolated {
+    receiver: Receiver<Event>,
+    timeout: Duration,
+    stopped: bool,
+}
+
+impl Isolated {
+    fn spawn(day: &Day, input: String, part: Option<usize>, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let builder = day.builder;
+        thread::spawn(move || {
+            let mut solution = match build(builder, input) {
+                Ok(solution) => solution,
+                Err(failure) => {
+                    let _ = sender.send(Event::Parse(failed((failure, Duration::ZERO))));
+                    return;
+                }
+            };
+            let parse = timed(|| solution.parse());
+            let parsed = parse.result.is_ok();
+            if sender.send(Event::Parse(parse)).is_err() || !parsed {
+                return;
+            }
+            // Part 1 runs for part 2 too, see run_phases.
+            for p in [1, 2].into_iter().filter(|p| *p == 1 || part != Some(1)) {
+                let result = match p {
+                    1 => timed(|| solution.part1()),
+                    _ => timed(|| solution.part2()),
+                };
+                if sender.send(Event::Part(result)).is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            receiver,
+            timeout,
+            stopped: false,
+        }
+    }
+
+    fn next(&mut self) -> Result<Event, (Failure, Duration)> {
+        if self.stopped {
+            return Err((Failure::Skipped, Duration::ZERO));
+        }
+        let result = self.receiver.recv_timeout(self.timeout);
+        self.stopped = result.is_err();
+        result.map_err(|e| match e {
+            RecvTimeoutError::Timeout => (Failure::Timeout(self.timeout), self.timeout),
+            RecvTimeoutError::Disconnected => (
+                Failure::Panicked("Solution thread stopped".into()),
+                Duration::ZERO,
+            ),
+        })
+    }
+}
+
+fn failed<T>((failure, duration): (Failure, Duration)) -> Timed<T> {
+    Timed {
+        result: Err(failure),
+        duration,
+        memory: None,
+    }
+}
+
+impl Phases for Isolated {
+    fn parse(&mut self) -> Timed<()> {
+        match self.next() {
+            Ok(Event::Parse(timed)) => timed,
+            Ok(Event::Part(_)) => unreachable!("Parsing is always the first phase"),
+            Err(e) => failed(e),
+        }
+    }
+
+    fn part(&mut self, _part: usize) -> Timed<Answer> {
+        match self.next() {
+            Ok(Event::Part(timed)) => timed,
+            Ok(Event::Parse(_)) => unreachable!("Parsing only happens once"),
+            Err(e) => failed(e),
+        }
+    }
+}
+
+/// A day whose solution panicked while being built, shown as a failure to parse.
+struct Unbuilt(Failure);
+
+impl Phases for Unbuilt {
+    fn parse(&mut self) -> Timed<()> {
+        failed((self.0.clone(), Duration::ZERO))
+    }
+
+    fn part(&mut self, _part: usize) -> Timed<Answer> {
+        Timed::skipped()
+    }
+}
+
+/// Build a day's solution, catching a panic in its constructor.
+fn build(builder: Builder, input: String) -> Result<Box<dyn Solution>, Failure> {
+    catch_unwind(AssertUnwindSafe(|| builder(input)))
+        .map_err(|payload| Failure::Panicked(panic_message(payload.as_ref())))
+}
+
+/// Run, print and check each phase of a day as it finishes.
+fn run_phases(
+    year: usize,
+    day: usize,
+    phases: &mut dyn Phases,
+    answers: &Answers,
+    part: Option<usize>,
+    out: &mut dyn Write,
+) -> Report {
+    let _ = writeln!(out, "---- {year}: {day:02} ----");
 
+    let parse = phases.parse();
+    print_phase(
+        out,
+        "Parsing",
+        parse.result.as_ref().map(|_| &""),
+        parse.duration,
+        parse.memory,
+        &Check::Unchecked,
+    );
+
+    let parts = [1, 2].map(|p| {
+        let wanted = part.is_none_or(|part| part == p);
+        // Part 1 still runs for part 2 as some days carry state between the parts,
+        // it's only shown if it fails.
+        if !(wanted || p == 1) || parse.result.is_err() {
+            return (Timed::skipped(), Check::Unchecked);
+        }
+        let timed = phases.part(p);
+        if !wanted && timed.result.is_ok() {
+            return (Timed::skipped(), Check::Unchecked);
+        }
+        let check = match wanted {
+            true => answers.check(day, p, &timed.result),
+            false => Check::Unchecked,
+        };
+        print_phase(
+            out,
+            &format!("Part {p}: "),
+            timed.result.as_ref(),
+            timed.duration,
+            timed.memory,
+            &check,
+        );
+        (timed, check)
+    });
+    let [(part1, check1), (part2, check2)] = parts;
+    Report {
+        year,
+        day,
+        parse,
+        part1,
+        part2,
+        check1,
+        check2,
+    }
+}
+
+/// Build a day's solution from the chosen input and run it, on a thread of its own
+/// when there's a timeout.
+fn run_day(day: &Day, options: &Options, answers: &Answers, out: &mut dyn Write) -> Report {
+    let input = options.input.clone().unwrap_or_else(|| day.default_input());
+    match options.timeout {
+        Some(timeout) => {
+            let mut phases = Isolated::spawn(day, input, options.part, timeout);
+            run_phases(day.year, day.day, &mut phases, answers, options.part, out)
+        }
+        None => match build(day.builder, input) {
+            Ok(mut solution) => run_to(solution.as_mut(), answers, options.part, out),
+            Err(failure) => {
+                let mut phases = Unbuilt(failure);
+                run_phases(day.year, day.day, &mut phases, answers, options.part, out)
+            }
+        },
+    }
+}
+
+/// Run days on a pool of `jobs` threads, 0 for one per core.
+/// Each day's output is buffered and printed in day order as soon as the days before it finish.
+fn run_parallel(days: &[&Day], options: &Options, answers: &Answers, jobs: usize) -> Vec<Report> {
+    let jobs = match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut reports = Vec::with_capacity(days.len());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            // Solutions aren't necessarily Send, so each one is built on the thread that runs it.
+            scope.spawn(move || {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let mut out = Vec::new();
+                    let report = run_day(day, options, answers, &mut out);
+                    if sender.send((report, out)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        for (report, out) in receiver {
+            pending.insert(report.day, (report, out));
+            while let Some(entry) = pending.first_entry() {
+                if days
+                    .get(reports.len())
+                    .is_none_or(|d| d.day != *entry.key())
+                {
+                    break;
+                }
+                let (report, out) = entry.remove();
+                let _ = io::stdout().write_all(&out);
+                reports.push(report);
+            }
+        }
+    });
+    reports
+}
+
+/// Format as `mmm:ss.fff`.
+fn format_total(duration: Duration) -> String {
+    let duration = duration.as_millis();
+    let millis = duration % 1000;
+    let seconds = duration / 1000;
+    let minutes = seconds / 60;
+    let seconds = seconds % 60;
+    format!("{minutes:3}:{seconds:02}.{millis:03}")
+}
+
+/// Time a phase, catching any error or panic.
+pub(crate) fn timed<T, F: FnOnce() -> Result<T, Error>>(phase: F) -> Timed<T> {
+    let tracker = Tracker::start();
     let start = Instant::now();
-    let part2 = solution.part2();
-    let end = start.elapsed();
-    print_solution(2, &part2, end);
+    let result = match catch_unwind(AssertUnwindSafe(phase)) {
+        Ok(Ok(v)) => Ok(v),
+        Ok(Err(e)) => Err(Failure::Error(e.to_string())),
+        Err(payload) => Err(Failure::Panicked(panic_message(payload.as_ref()))),
+    };
+    let duration = start.elapsed();
+    Timed {
+        result,
+        duration,
+        memory: tracker.finish(),
+    }
 }
 
-fn print_solution(which: usize, output: &str, duration: Duration) {
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "Unknown panic".into()
+    }
+}
+
+fn print_phase<T: Display>(
+    out: &mut dyn Write,
+    label: &str,
+    output: Result<&T, &Failure>,
+    duration: Duration,
+    memory: Option<AllocStats>,
+    check: &Check,
+) {
     let ms = duration.as_millis();
     let sec_part = ms / 1000;
     let ms_part = ms % 1000;
 
-    println!("{sec_part:3}.{ms_part:03} Part {which}: {output}");
-    // let mut i = output.iter();
-    // println!(
-    //     "{sec_part:3}.{ms_part:03} Part {which}: {}",
-    //     i.next().unwrap()
-    // );
-    // for line in i {
-    //     println!("{:16}{line}", "");
-    // }
+    let output = match output {
+        Ok(v) => v.to_string(),
+        Err(e) => e.to_string(),
+    };
+    let check = match check {
+        Check::Unchecked => String::new(),
+        check => format!(" {check}"),
+    };
+    let memory = memory.map_or(String::new(), |m| format!(" ({m})"));
+    // Multi line answers are lined up under the first line.
+    let mut lines = output.lines();
+    let _ = writeln!(
+        out,
+        "{sec_part:3}.{ms_part:03} {label}{}{check}{memory}",
+        lines.next().unwrap_or_default()
+    );
+    for line in lines {
+        let _ = writeln!(out, "{:16}{line}", "");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example {
+        day: usize,
+        input: String,
+    }
+
+    impl Runner for Example {
+        fn name(&self) -> (usize, usize) {
+            (2015, self.day)
+        }
+
+        fn parse(&mut self) {}
+
+        fn part1(&mut self) -> String {
+            output(&self.input)
+        }
+
+        fn part2(&mut self) -> String {
+            output(self.day)
+        }
+    }
+
+    fn day2(input: String) -> Box<dyn Solution> {
+        Box::new(Example { day: 2, input })
+    }
+
+    fn day7(input: String) -> Box<dyn Solution> {
+        Box::new(Example { day: 7, input })
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register(day7);
+        registry.register(day2);
+        assert_eq!(
+            vec![(2015, 2), (2015, 7)],
+            registry.iter().map(|d| (d.year, d.day)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(7), registry.last().map(|d| d.day));
+        assert!(registry.get(3).is_none());
+        let day = registry.get(2).unwrap();
+        assert_eq!("aoc2015/inputs/day02.txt", day.default_input());
+        assert_eq!(
+            Answer::Text("aoc2015/inputs/day02.txt".into()),
+            day.load().part1().unwrap()
+        );
+        assert_eq!(
+            Answer::Text("text".into()),
+            day.build("text").part1().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let parse = |args: &[&str]| Options::parse(args.iter().map(|a| a.to_string()));
+        assert_eq!(Ok(Options::default()), parse(&[]));
+        let options = parse(&["--verify", "7"]).unwrap();
+        assert_eq!(Some(7), options.day);
+        assert!(options.verify && !options.record);
+        assert!(parse(&["1", "2"]).is_err());
+        assert!(parse(&["--fast"]).is_err());
+        let options = parse(&["0", "--iterations", "50", "--json", "-"]).unwrap();
+        assert_eq!(
+            Some(BenchOptions {
+                warmup: 1,
+                iterations: 50
+            }),
+            options.bench
+        );
+        assert_eq!(Some(PathBuf::from("-")), options.json);
+        assert!(parse(&["--warmup", "x"]).is_err());
+        assert!(parse(&["--json"]).is_err());
+        let options = parse(&["3", "--part", "2", "--input", "example.txt"]).unwrap();
+        assert_eq!(Some(2), options.part);
+        assert_eq!(Some("example.txt".into()), options.input);
+        assert!(parse(&["--part", "3"]).is_err());
+
+        let mut registry = Registry::new();
+        registry.register(day7);
+        registry.register(day2);
+        let days = |options: &Options| {
+            registry
+                .selected(options)
+                .iter()
+                .map(|d| d.day)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![7], days(&parse(&[]).unwrap()));
+        assert_eq!(vec![2, 7], days(&parse(&["--verify"]).unwrap()));
+        assert_eq!(vec![2], days(&parse(&["--verify", "2"]).unwrap()));
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let mut registry = Registry::new();
+        registry.register(day7);
+        registry.register(day2);
+        let options = Options {
+            day: Some(0),
+            jobs: Some(2),
+            ..Default::default()
+        };
+        let reports = registry.run(&options);
+        assert_eq!(
+            vec![2, 7],
+            reports.iter().map(|r| r.day).collect::<Vec<_>>()
+        );
+        assert_eq!(Ok(Answer::UInt(7)), reports[1].part2.result);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_duplicate() {
+        let mut registry = Registry::new();
+        registry.register(day2);
+        registry.register(day2);
+    }
+
+    #[test]
+    fn test_answer_from_output() {
+        assert_eq!(Ok(Answer::UInt(42)), "42".parse());
+        assert_eq!(Ok(Answer::Int(-42)), "-42".parse());
+        assert_eq!(Ok(Answer::Text("abc".into())), "abc".parse());
+        assert_eq!(Ok(Answer::Text("0123".into())), "0123".parse());
+        assert_eq!(Ok(Answer::Text("+5".into())), "+5".parse());
+        assert_eq!(Ok(Answer::Text("-0".into())), "-0".parse());
+        assert_eq!("0123", "0123".parse::<Answer>().unwrap().to_string());
+        assert_eq!(Ok(Answer::UInt(0)), "0".parse());
+        assert_eq!(Ok(Answer::Art("#.\n.#".into())), "#.\n.#".parse());
+        assert_eq!(Ok(Answer::Unsolved), "Unsolved".parse());
+        assert_eq!(Answer::UInt(3), 3_usize.into());
+        assert_eq!(Answer::Int(-3), (-3_i32).into());
+    }
+
+    struct Broken;
+
+    impl Solution for Broken {
+        fn name(&self) -> (usize, usize) {
+            (2015, 1)
+        }
+
+        fn parse(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Answer, Error> {
+            Err("no answer".into())
+        }
+
+        fn part2(&mut self) -> Result<Answer, Error> {
+            panic!("boom")
+        }
+    }
+
+    #[test]
+    fn test_run_solution_failures() {
+        let report = run_solution(&mut Broken);
+        assert_eq!(Ok(()), report.parse.result);
+        assert_eq!(Err(Failure::Error("no answer".into())), report.part1.result);
+        assert_eq!(Err(Failure::Panicked("boom".into())), report.part2.result);
+        assert!(report.failed());
+
+        let report = run_checked(&mut Broken, &Answers::default(), Some(2));
+        assert_eq!(Err(Failure::Error("no answer".into())), report.part1.result);
+        assert_eq!(Err(Failure::Panicked("boom".into())), report.part2.result);
+        let report = run_checked(&mut Broken, &Answers::default(), Some(1));
+        assert_eq!(Err(Failure::Skipped), report.part2.result);
+    }
+
+    /// Part 2 needs what part 1 found.
+    struct Carry(u64);
+
+    impl Solution for Carry {
+        fn name(&self) -> (usize, usize) {
+            (2015, 1)
+        }
+
+        fn parse(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Answer, Error> {
+            self.0 = 21;
+            Ok(Answer::UInt(self.0))
+        }
+
+        fn part2(&mut self) -> Result<Answer, Error> {
+            Ok(Answer::UInt(self.0 * 2))
+        }
+    }
+
+    #[test]
+    fn test_part_two_only() {
+        let mut out = Vec::new();
+        let report = run_to(&mut Carry(0), &Answers::default(), Some(2), &mut out);
+        assert_eq!(Err(Failure::Skipped), report.part1.result);
+        assert_eq!(Ok(Answer::UInt(42)), report.part2.result);
+        assert!(!report.failed());
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("Part 1: "));
+        assert!(out.contains("Part 2: 42"));
+
+        let mut registry = Registry::new();
+        registry.register(|_| Box::new(Carry(0)));
+        let options = Options {
+            part: Some(2),
+            timeout: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let reports = registry.run(&options);
+        assert_eq!(Err(Failure::Skipped), reports[0].part1.result);
+        assert_eq!(Ok(Answer::UInt(42)), reports[0].part2.result);
+    }
+
+    /// Output shared with the solution writing it, so a part can see what was printed before it ran.
+    #[derive(Clone, Default)]
+    struct Shared(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Shared {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Records what had been printed when part 2 started.
+    struct Watched(Shared, String);
+
+    impl Solution for Watched {
+        fn name(&self) -> (usize, usize) {
+            (2015, 1)
+        }
+
+        fn parse(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Answer, Error> {
+            Ok(Answer::UInt(1))
+        }
+
+        fn part2(&mut self) -> Result<Answer, Error> {
+            self.1 = self.0.text();
+            Ok(Answer::UInt(2))
+        }
+    }
+
+    #[test]
+    fn test_parts_print_as_they_finish() {
+        let mut out = Shared::default();
+        let mut solution = Watched(out.clone(), String::new());
+        run_to(&mut solution, &Answers::default(), None, &mut out);
+        assert!(solution.1.contains("Part 1: "));
+        assert!(!solution.1.contains("Part 2: "));
+        assert!(out.text().contains("Part 2: "));
+    }
+
+    struct Slow(usize);
+
+    impl Solution for Slow {
+        fn name(&self) -> (usize, usize) {
+            (2015, self.0)
+        }
+
+        fn parse(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Answer, Error> {
+            if self.0 == 1 {
+                thread::sleep(Duration::from_secs(2));
+            }
+            Ok(Answer::UInt(1))
+        }
+
+        fn part2(&mut self) -> Result<Answer, Error> {
+            match self.0 {
+                2 => panic!("boom"),
+                _ => Ok(Answer::UInt(2)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut registry = Registry::new();
+        registry.register(|_| Box::new(Slow(1)));
+        registry.register(|_| Box::new(Slow(2)));
+        registry.register(|_| Box::new(Slow(3)));
+        let options = Options {
+            day: Some(0),
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let reports = registry.run(&options);
+        let timeout = Failure::Timeout(Duration::from_millis(100));
+        assert_eq!(Err(timeout), reports[0].part1.result);
+        assert_eq!(Err(Failure::Skipped), reports[0].part2.result);
+        assert_eq!(Ok(Answer::UInt(1)), reports[1].part1.result);
+        assert_eq!(
+            Err(Failure::Panicked("boom".into())),
+            reports[1].part2.result
+        );
+        assert_eq!(Ok(Answer::UInt(2)), reports[2].part2.result);
+
+        assert_eq!(
+            Ok(Some(Duration::from_millis(1500))),
+            Options::parse(["--timeout".to_string(), "1.5".to_string()]).map(|o| o.timeout)
+        );
+        for bad in ["-1", "0", "1e30", "inf", "NaN"] {
+            assert!(Options::parse(["--timeout".to_string(), bad.to_string()]).is_err());
+        }
+    }
+
+    /// A solution that panics while being built fails to parse instead of stopping the run.
+    #[test]
+    fn test_build_panic() {
+        let mut registry = Registry::new();
+        registry.register(|input| {
+            assert!(input.is_empty(), "No such input");
+            Box::new(Carry(0))
+        });
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let reports = registry.run(&Options {
+                timeout,
+                ..Default::default()
+            });
+            let panicked = Failure::Panicked("No such input".into());
+            assert_eq!(Err(panicked), reports[0].parse.result);
+            assert_eq!(Err(Failure::Skipped), reports[0].part1.result);
+        }
+    }
 }
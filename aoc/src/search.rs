@@ -16,43 +16,156 @@ pub trait Searcher<G: Graph>: Eq + Hash + Clone {
     fn is_done(&self, graph: &G) -> bool;
 }
 
+/// A found path along with how much work it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S> {
+    /// Nodes from start to the done node inclusive.
+    pub path: Vec<S>,
+    /// Number of nodes expanded.
+    pub visited: usize,
+    /// Number of steps from start to the done node.
+    pub depth: usize,
+}
+
+impl<S> SearchResult<S> {
+    fn new(path: Vec<S>, visited: usize) -> Self {
+        let depth = path.len().saturating_sub(1);
+        Self {
+            path,
+            visited,
+            depth,
+        }
+    }
+}
+
 pub fn dfs<S: Searcher<G>, G: Graph>(start: &S, graph: &G) -> Option<Vec<S>> {
+    dfs_search(start, graph).map(|r| r.path)
+}
+
+/// Depth first search recording the node each visited node was first reached from.
+pub fn dfs_search<S: Searcher<G>, G: Graph>(start: &S, graph: &G) -> Option<SearchResult<S>> {
     let mut path = HashMap::new();
-    let mut to_visit = vec![start.clone()];
-    while let Some(node) = to_visit.pop() {
-        if path.contains_key(&node) {
+    let mut seen = HashSet::new();
+    let mut to_visit = vec![(start.clone(), None)];
+    while let Some((node, from)) = to_visit.pop() {
+        if !seen.insert(node.clone()) {
             continue;
         }
+        if let Some(from) = from {
+            path.insert(node.clone(), from);
+        }
         if node.is_done(graph) {
-            return Some(get_path(path, node, start));
+            return Some(SearchResult::new(get_path(path, node, start), seen.len()));
         }
         for next_move in node.moves(graph) {
-            to_visit.push(next_move.clone());
-            path.insert(next_move.clone(), node.clone());
+            if !seen.contains(&next_move) {
+                to_visit.push((next_move, Some(node.clone())));
+            }
         }
     }
     None
 }
 
 pub fn bfs<S: Searcher<G>, G: Graph>(start: &S, graph: &G) -> Option<Vec<S>> {
+    bfs_search(start, graph).map(|r| r.path)
+}
+
+/// Breadth first search, the returned path has the fewest steps.
+pub fn bfs_search<S: Searcher<G>, G: Graph>(start: &S, graph: &G) -> Option<SearchResult<S>> {
     let mut path = HashMap::new();
-    let mut to_visit = VecDeque::new();
-    to_visit.push_front(start.clone());
+    let mut seen = HashSet::from([start.clone()]);
+    let mut to_visit = VecDeque::from([start.clone()]);
+    let mut visited = 0;
     while let Some(node) = to_visit.pop_front() {
+        visited += 1;
         if node.is_done(graph) {
-            return Some(get_path(path, node, start));
+            return Some(SearchResult::new(get_path(path, node, start), visited));
         }
         for next_move in node.moves(graph) {
-            if path.contains_key(&next_move) {
-                continue;
+            if seen.insert(next_move.clone()) {
+                to_visit.push_back(next_move.clone());
+                path.insert(next_move, node.clone());
             }
-            to_visit.push_back(next_move.clone());
-            path.insert(next_move.clone(), node.clone());
         }
     }
     None
 }
 
+/// Flood fill from start giving the fewest steps to every reachable node. Ignores `is_done`.
+pub fn bfs_all<S: Searcher<G>, G: Graph>(start: &S, graph: &G) -> HashMap<S, usize> {
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut to_visit = VecDeque::from([start.clone()]);
+    while let Some(node) = to_visit.pop_front() {
+        let steps = dist[&node] + 1;
+        for next_move in node.moves(graph) {
+            if !dist.contains_key(&next_move) {
+                dist.insert(next_move.clone(), steps);
+                to_visit.push_back(next_move);
+            }
+        }
+    }
+    dist
+}
+
+/// Depth first search that gives up on paths longer than limit steps.
+/// Only nodes on the current path are avoided, so nodes may be expanded more than once.
+pub fn dfs_limited<S: Searcher<G>, G: Graph>(
+    start: &S,
+    graph: &G,
+    limit: usize,
+) -> Option<SearchResult<S>> {
+    let mut path = vec![start.clone()];
+    let mut visited = 0;
+    match limited(graph, limit, &mut path, &mut visited) {
+        true => Some(SearchResult::new(path, visited)),
+        false => None,
+    }
+}
+
+/// Run `dfs_limited` with increasing limits up to max_depth, finding the shallowest path
+/// while only holding the current path in memory. Visited counts every expansion across runs.
+pub fn iddfs<S: Searcher<G>, G: Graph>(
+    start: &S,
+    graph: &G,
+    max_depth: usize,
+) -> Option<SearchResult<S>> {
+    let mut visited = 0;
+    for limit in 0..=max_depth {
+        let mut path = vec![start.clone()];
+        if limited(graph, limit, &mut path, &mut visited) {
+            return Some(SearchResult::new(path, visited));
+        }
+    }
+    None
+}
+
+fn limited<S: Searcher<G>, G: Graph>(
+    graph: &G,
+    limit: usize,
+    path: &mut Vec<S>,
+    visited: &mut usize,
+) -> bool {
+    *visited += 1;
+    let node = path.last().unwrap().clone();
+    if node.is_done(graph) {
+        return true;
+    }
+    if limit == 0 {
+        return false;
+    }
+    for next_move in node.moves(graph) {
+        if path.contains(&next_move) {
+            continue;
+        }
+        path.push(next_move);
+        if limited(graph, limit - 1, path, visited) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct MinHeapState<S: Hash + Ord + PartialOrd + Eq + PartialEq> {
     node: S,
@@ -79,6 +192,11 @@ pub trait Weighted {
     fn weight(&self, cur: &Self::Node, next: &Self::Node) -> usize;
     fn moves(&self, cur: &Self::Node) -> Vec<Self::Node>;
     fn is_done(&self, node: &Self::Node) -> bool;
+    /// Estimate of the remaining cost to a done node, used by `astar`.
+    /// Must never overestimate. The default of 0 makes `astar` behave like `dijkstra`.
+    fn heuristic(&self, _node: &Self::Node) -> usize {
+        0
+    }
 }
 
 pub fn dijkstra<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
@@ -126,18 +244,229 @@ pub fn dijkstra<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
     None
 }
 
+/// How far `dijkstra_paths` should search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Explore {
+    /// Stop once every done node tied for the cheapest cost has been reached.
+    UntilDone,
+    /// Visit every reachable node, ignoring `is_done`.
+    All,
+}
+
+/// Dijkstra keeping every predecessor that lies on a shortest path.
+/// Edge weights must be positive for the predecessor lists to be complete.
+pub fn dijkstra_paths<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+    explore: Explore,
+) -> ShortestPaths<N> {
+    let mut heap: BinaryHeap<MinHeapState<N>> = BinaryHeap::new();
+    let mut dist: HashMap<N, usize> = HashMap::new();
+    let mut previous: HashMap<N, Vec<N>> = HashMap::new();
+    let mut ends: Vec<N> = Vec::new();
+
+    heap.push(MinHeapState {
+        node: start.clone(),
+        cost: 0,
+    });
+    dist.insert(start.clone(), 0);
+
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        // Already have a better path to node.
+        if cost > dist[&node] {
+            continue;
+        }
+
+        if explore == Explore::UntilDone {
+            // Every tied done node has been found.
+            if ends.first().is_some_and(|end| cost > dist[end]) {
+                break;
+            }
+            if graph.is_done(&node) {
+                ends.push(node);
+                continue;
+            }
+        } else if graph.is_done(&node) {
+            ends.push(node.clone());
+        }
+
+        for next_move in graph.moves(&node) {
+            let next_cost = cost + graph.weight(&node, &next_move);
+            match dist.get(&next_move) {
+                Some(d) if next_cost > *d => (),
+                Some(d) if next_cost == *d => {
+                    previous.entry(next_move).or_default().push(node.clone());
+                }
+                _ => {
+                    heap.push(MinHeapState {
+                        node: next_move.clone(),
+                        cost: next_cost,
+                    });
+                    dist.insert(next_move.clone(), next_cost);
+                    previous.insert(next_move, vec![node.clone()]);
+                }
+            }
+        }
+    }
+
+    ShortestPaths {
+        start: start.clone(),
+        dist,
+        previous,
+        ends,
+    }
+}
+
+/// Distances and the predecessor DAG produced by `dijkstra_paths`.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N: Hash + Eq> {
+    pub start: N,
+    /// Cheapest known cost to each visited node.
+    pub dist: HashMap<N, usize>,
+    /// Every neighbour that reaches a node at its cheapest cost.
+    pub previous: HashMap<N, Vec<N>>,
+    /// Done nodes found, cheapest first.
+    pub ends: Vec<N>,
+}
+
+impl<N: Hash + Eq + Clone> ShortestPaths<N> {
+    /// Cost of the cheapest done node.
+    pub fn cost(&self) -> Option<usize> {
+        self.ends.first().map(|end| self.dist[end])
+    }
+
+    /// Done nodes tied for the cheapest cost.
+    pub fn best_ends(&self) -> impl Iterator<Item = &N> {
+        let best = self.cost();
+        self.ends
+            .iter()
+            .filter(move |end| Some(self.dist[*end]) == best)
+    }
+
+    /// One cheapest path from start to the cheapest done node.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.ends.first().and_then(|end| self.path_to(end))
+    }
+
+    /// One cheapest path from start to node.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.paths_to(node).next()
+    }
+
+    /// Every tied cheapest path from start to a tied cheapest done node.
+    pub fn paths(&self) -> impl Iterator<Item = Vec<N>> {
+        self.best_ends().flat_map(|end| self.paths_to(end))
+    }
+
+    /// Every tied cheapest path from start to node.
+    pub fn paths_to(&self, node: &N) -> AllPaths<'_, N> {
+        let stack = match self.dist.contains_key(node) {
+            true => vec![vec![node.clone()]],
+            false => vec![],
+        };
+        AllPaths { paths: self, stack }
+    }
+
+    /// Every node that lies on at least one of the tied cheapest paths.
+    pub fn on_best_paths(&self) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut to_visit = self.best_ends().cloned().collect::<Vec<_>>();
+        while let Some(node) = to_visit.pop() {
+            if !seen.insert(node.clone()) {
+                continue;
+            }
+            if let Some(prev) = self.previous.get(&node) {
+                to_visit.extend(prev.iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Lazy walk back through the predecessor DAG yielding each path start first.
+pub struct AllPaths<'a, N: Hash + Eq> {
+    paths: &'a ShortestPaths<N>,
+    stack: Vec<Vec<N>>,
+}
+
+impl<N: Hash + Eq + Clone> Iterator for AllPaths<'_, N> {
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(mut path) = self.stack.pop() {
+            let node = path.last().unwrap();
+            if *node == self.paths.start {
+                path.reverse();
+                return Some(path);
+            }
+            for prev in self.paths.previous.get(node).into_iter().flatten() {
+                let mut next = path.clone();
+                next.push(prev.clone());
+                self.stack.push(next);
+            }
+        }
+        None
+    }
+}
+
+/// Find the cheapest path to a done node guided by `Weighted::heuristic`.
+/// Returns the cost and the path from start to the done node.
+pub fn astar<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+) -> Option<(usize, Vec<N>)> {
+    let mut heap: BinaryHeap<MinHeapState<N>> = BinaryHeap::new();
+    let mut dist: HashMap<N, usize> = HashMap::new();
+    let mut previous: HashMap<N, N> = HashMap::new();
+
+    heap.push(MinHeapState {
+        node: start.clone(),
+        cost: graph.heuristic(start),
+    });
+    dist.insert(start.clone(), 0);
+
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        let so_far = dist[&node];
+        if graph.is_done(&node) {
+            return Some((so_far, get_path(previous, node, start)));
+        }
+
+        // Already have a better path to node.
+        if cost > so_far + graph.heuristic(&node) {
+            continue;
+        }
+
+        for next_move in graph.moves(&node) {
+            let next_cost = so_far + graph.weight(&node, &next_move);
+            if dist.get(&next_move).is_none_or(|d| next_cost < *d) {
+                heap.push(MinHeapState {
+                    node: next_move.clone(),
+                    cost: next_cost + graph.heuristic(&next_move),
+                });
+                dist.insert(next_move.clone(), next_cost);
+                previous.insert(next_move, node.clone());
+            }
+        }
+    }
+
+    None
+}
+
+/// Walk the predecessor map back from end to start.
+/// Stops early if the chain ends or loops before reaching start.
 pub fn get_path<S: PartialEq + Eq + Hash + Clone>(
     moves: HashMap<S, S>,
     end: S,
     start: &S,
 ) -> Vec<S> {
-    let mut found = Vec::new();
-    found.push(end);
-    while let Some(node) = moves.get(found.last().unwrap()) {
+    let mut seen = HashSet::from([end.clone()]);
+    let mut found = vec![end];
+    while let Some(last) = found.last()
+        && last != start
+        && let Some(node) = moves.get(last)
+        && seen.insert(node.clone())
+    {
         found.push(node.clone());
-        if node == start {
-            break;
-        }
     }
     found.reverse();
     found
@@ -178,6 +507,186 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_astar() {
+        let graph = Layout {
+            nodes: vec![
+                vec![(2, 10), (1, 1)],
+                vec![(3, 2)],
+                vec![(1, 1), (3, 3), (4, 1)],
+                vec![(0, 7), (4, 2)],
+                vec![],
+            ],
+            target: 4,
+        };
+        assert_eq!(Some((5, vec![0, 1, 3, 4])), astar(&0, &graph));
+        assert_eq!(Some((0, vec![4])), astar(&4, &graph));
+        assert_eq!(None, astar(&4, &Layout { target: 0, ..graph }));
+    }
+
+    #[test]
+    fn test_astar_heuristic() {
+        // Open 5x5 room with a wall down the middle except the bottom row.
+        let graph = Room {
+            walls: (0..4).map(|r| (r, 2)).collect(),
+            size: 5,
+            target: (0, 4),
+        };
+        let (cost, path) = astar(&(0, 0), &graph).unwrap();
+        assert_eq!(12, cost);
+        assert_eq!(13, path.len());
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(0, 4)), path.last());
+        assert_eq!(
+            Some(cost),
+            dijkstra(&(0, 0), &graph).map(|d| d[&graph.target])
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_paths() {
+        // Two tied routes from 0 to 3 and a longer one through 4.
+        let graph = Layout {
+            nodes: vec![
+                vec![(1, 1), (2, 1), (4, 1)],
+                vec![(3, 1)],
+                vec![(3, 1)],
+                vec![(5, 1)],
+                vec![(3, 5)],
+                vec![],
+            ],
+            target: 3,
+        };
+        let found = dijkstra_paths(&0, &graph, Explore::UntilDone);
+        assert_eq!(Some(2), found.cost());
+        assert_eq!(vec![&3], found.best_ends().collect::<Vec<_>>());
+        assert!(!found.dist.contains_key(&5));
+        let mut paths = found.paths().collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(vec![vec![0, 1, 3], vec![0, 2, 3]], paths);
+        assert_eq!(Some(3), found.path().map(|p| p.len()));
+        let nodes = found.on_best_paths();
+        assert_eq!(HashSet::from([0, 1, 2, 3]), nodes);
+
+        let everything = dijkstra_paths(&0, &graph, Explore::All);
+        assert_eq!(Some(&3), everything.dist.get(&5));
+        assert_eq!(2, everything.paths_to(&5).count());
+        assert_eq!(Some(vec![0, 4]), everything.path_to(&4));
+        assert_eq!(None, everything.path_to(&6));
+    }
+
+    #[test]
+    fn test_dfs() {
+        // 0 reaches 1 directly and through 2.
+        let graph = Maze {
+            edges: vec![vec![1, 2], vec![3], vec![1], vec![]],
+            target: 3,
+        };
+        let actual = dfs(&0, &graph).unwrap();
+        assert_eq!(Some(&0), actual.first());
+        assert_eq!(Some(&3), actual.last());
+        assert!(actual.windows(2).all(|w| graph.edges[w[0]].contains(&w[1])));
+        assert_eq!(None, dfs(&3, &Maze { target: 0, ..graph }));
+    }
+
+    #[test]
+    fn test_bfs() {
+        let graph = Maze {
+            edges: vec![vec![1, 2], vec![3], vec![3], vec![4], vec![0], vec![]],
+            target: 4,
+        };
+        let expected = SearchResult {
+            path: vec![0, 1, 3, 4],
+            visited: 5,
+            depth: 3,
+        };
+        assert_eq!(Some(expected), bfs_search(&0, &graph));
+        assert_eq!(Some(vec![4]), bfs(&4, &graph));
+        assert_eq!(None, bfs(&5, &graph));
+        let expected = HashMap::from([(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+        assert_eq!(expected, bfs_all(&0, &graph));
+    }
+
+    #[test]
+    fn test_depth_limited() {
+        let graph = Maze {
+            edges: vec![vec![1, 2], vec![2], vec![3], vec![4], vec![0], vec![]],
+            target: 4,
+        };
+        assert_eq!(None, dfs_limited(&0, &graph, 2));
+        let found = dfs_limited(&0, &graph, 10).unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 4], found.path);
+        let found = iddfs(&0, &graph, 10).unwrap();
+        assert_eq!(vec![0, 2, 3, 4], found.path);
+        assert_eq!(3, found.depth);
+        assert_eq!(None, iddfs(&5, &graph, 10));
+    }
+
+    #[test]
+    fn test_get_path_without_start() {
+        let moves = HashMap::from([(1, 2), (2, 1)]);
+        assert_eq!(vec![2, 1], get_path(moves, 1, &0));
+    }
+
+    struct Maze {
+        edges: Vec<Vec<usize>>,
+        target: usize,
+    }
+
+    impl Graph for Maze {
+        fn height(&self) -> usize {
+            self.edges.len()
+        }
+
+        fn width(&self) -> usize {
+            1
+        }
+    }
+
+    impl Searcher<Maze> for usize {
+        fn moves(&self, graph: &Maze) -> Vec<Self> {
+            graph.edges[*self].clone()
+        }
+
+        fn is_done(&self, graph: &Maze) -> bool {
+            *self == graph.target
+        }
+    }
+
+    struct Room {
+        walls: HashSet<(i64, i64)>,
+        size: i64,
+        target: (i64, i64),
+    }
+
+    impl Weighted for Room {
+        type Node = (i64, i64);
+
+        fn weight(&self, _cur: &(i64, i64), _next: &(i64, i64)) -> usize {
+            1
+        }
+
+        fn moves(&self, cur: &(i64, i64)) -> Vec<(i64, i64)> {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .iter()
+                .map(|d| (cur.0 + d.0, cur.1 + d.1))
+                .filter(|p| {
+                    (0..self.size).contains(&p.0)
+                        && (0..self.size).contains(&p.1)
+                        && !self.walls.contains(p)
+                })
+                .collect()
+        }
+
+        fn is_done(&self, node: &(i64, i64)) -> bool {
+            *node == self.target
+        }
+
+        fn heuristic(&self, node: &(i64, i64)) -> usize {
+            (node.0.abs_diff(self.target.0) + node.1.abs_diff(self.target.1)) as usize
+        }
+    }
+
     struct Layout {
         nodes: Vec<Vec<(usize, usize)>>,
         target: usize,
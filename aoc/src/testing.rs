@@ -0,0 +1,422 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::runner::{Answer, Solution};
+
+/// A puzzle example with its expected answers, stored as `aocYYYY/examples/dayNN-NAME.txt`:
+/// ```text
+/// part1: 41
+/// part2: 6
+/// ---
+/// ....#.....
+/// ```
+/// Either part may be left out to only check the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub input: String,
+}
+
+impl Example {
+    /// Where a year crate keeps a day's example.
+    pub fn path<P: AsRef<Path>>(manifest_dir: P, day: usize, name: &str) -> PathBuf {
+        manifest_dir
+            .as_ref()
+            .join("examples")
+            .join(format!("day{day:02}-{name}.txt"))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        text.parse().map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Write the input to a temporary file for the solution to read like a real input,
+    /// so the text is never mistaken for a path. `path` is the example's own file.
+    fn input_file(&self, path: &Path) -> Result<PathBuf, String> {
+        let name = path
+            .file_name()
+            .map_or("example.txt".into(), |name| name.to_string_lossy());
+        let file = std::env::temp_dir().join(format!("aoc-example-{}-{name}", std::process::id()));
+        fs::write(&file, &self.input)
+            .map_err(|e| format!("Could not write {}: {e}", file.display()))?;
+        Ok(file)
+    }
+
+    /// Run the example through a solution and check the expected answers.
+    /// Part 1 always runs first, as it does in a full run of the day.
+    pub fn check<T: Solution + ?Sized>(&self, solution: &mut T) -> Result<(), String> {
+        solution
+            .parse()
+            .map_err(|e| format!("Parsing failed: {e}"))?;
+        let part1 = solution.part1();
+        if let Some(expected) = &self.part1 {
+            compare(1, expected, part1)?;
+        }
+        if let Some(expected) = &self.part2 {
+            compare(2, expected, solution.part2())?;
+        }
+        Ok(())
+    }
+}
+
+fn compare<E: std::fmt::Display>(
+    part: usize,
+    expected: &str,
+    actual: Result<Answer, E>,
+) -> Result<(), String> {
+    match actual {
+        Ok(answer) if answer.to_string() == expected => Ok(()),
+        Ok(answer) => Err(format!(
+            "Part {part}: expected `{expected}`, got `{answer}`"
+        )),
+        Err(e) => Err(format!("Part {part} failed: {e}")),
+    }
+}
+
+impl std::str::FromStr for Example {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.replace("\r\n", "\n");
+        let (header, input) = s
+            .split_once("\n---\n")
+            .or_else(|| s.strip_prefix("---\n").map(|input| ("", input)))
+            .ok_or("Missing `---` line after the expected answers")?;
+        let mut example = Self {
+            part1: None,
+            part2: None,
+            input: input.to_string(),
+        };
+        for line in header.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Expected `part1: ANSWER`, found `{line}`"))?;
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "part1" => example.part1 = value,
+                "part2" => example.part2 = value,
+                key => return Err(format!("Unknown key `{key}`")),
+            }
+        }
+        Ok(example)
+    }
+}
+
+impl std::fmt::Display for Example {
+    /// The file format `FromStr` reads.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(part1) = &self.part1 {
+            writeln!(f, "part1: {part1}")?;
+        }
+        if let Some(part2) = &self.part2 {
+            writeln!(f, "part2: {part2}")?;
+        }
+        write!(f, "---\n{}", self.input)
+    }
+}
+
+/// Names of a day's examples, for `dayNN-NAME.txt` in the crate's examples directory.
+pub fn example_names<P: AsRef<Path>>(manifest_dir: P, day: usize) -> Vec<String> {
+    let prefix = format!("day{day:02}-");
+    let Ok(entries) = fs::read_dir(manifest_dir.as_ref().join("examples")) else {
+        return Vec::new();
+    };
+    let mut names = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file| {
+            let name = file.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+            Some(name.to_string())
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+/// Load an example and check it against a solution built from its input, panicking on any mismatch.
+pub fn run_example<S: Solution, F: FnOnce(String) -> S>(
+    manifest_dir: &str,
+    day: usize,
+    name: &str,
+    build: F,
+) {
+    let path = Example::path(manifest_dir, day, name);
+    let example = Example::load(&path).unwrap_or_else(|e| panic!("{e}"));
+    let input = example.input_file(&path).unwrap_or_else(|e| panic!("{e}"));
+    let mut solution = build(input.display().to_string());
+    let checked = example.check(&mut solution);
+    let _ = fs::remove_file(&input);
+    if let Err(e) = checked {
+        panic!("{}: {e}", path.display());
+    }
+}
+
+/// `run_example` for a day in the binary per day layout of `templates/bin.rs`, where
+/// `parse` reads the input from a file and both parts answer from its model.
+pub fn run_bin_example<M, A, B, P, F1, F2>(
+    manifest_dir: &str,
+    day: usize,
+    name: &str,
+    parse: P,
+    part1: F1,
+    part2: F2,
+) where
+    A: std::fmt::Display,
+    B: std::fmt::Display,
+    P: FnOnce(String) -> M,
+    F1: FnOnce(&M) -> A,
+    F2: FnOnce(&M) -> B,
+{
+    let path = Example::path(manifest_dir, day, name);
+    let check = |example: Example| {
+        let input = example.input_file(&path)?;
+        let model = parse(input.display().to_string());
+        let _ = fs::remove_file(&input);
+        let answer = part1(&model).to_string();
+        if let Some(expected) = &example.part1 {
+            compare(1, expected, Ok::<_, String>(answer.into()))?;
+        }
+        if let Some(expected) = &example.part2 {
+            let answer = part2(&model).to_string();
+            compare(2, expected, Ok::<_, String>(answer.into()))?;
+        }
+        Ok(())
+    };
+    if let Err(e) = Example::load(&path).and_then(check) {
+        panic!("{}: {e}", path.display());
+    }
+}
+
+/// Panic if any example of a day isn't in `listed`, so a new example file can't go untested.
+pub fn assert_listed(manifest_dir: &str, day: usize, listed: &[&str]) {
+    let unlisted = example_names(manifest_dir, day)
+        .into_iter()
+        .filter(|name| !listed.contains(&name.as_str()))
+        .collect::<Vec<_>>();
+    assert!(
+        unlisted.is_empty(),
+        "Examples for day {day} with no test: {}",
+        unlisted.join(", ")
+    );
+}
+
+/// Test a day against its examples, `examples/dayNN-NAME.txt` in the crate being tested.
+/// Each listed example gets a test of its own, and `all_examples_listed` fails for any
+/// example file of the day that isn't listed. `build_day examples` lists the files it saves.
+/// ```ignore
+/// #[cfg(test)]
+/// mod test {
+///     use super::*;
+///
+///     aoc::examples!(6, AocDay {
+///         example_1: "1",
+///         larger: "larger",
+///     });
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($day:literal, $solution:ident { $($test:ident: $file:literal),* $(,)? }) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::testing::run_example(
+                    env!("CARGO_MANIFEST_DIR"),
+                    $day,
+                    $file,
+                    $solution::new,
+                );
+            }
+        )*
+
+        #[test]
+        fn all_examples_listed() {
+            // Keeps the solution in use while no examples are listed.
+            let _: fn(String) -> $solution = $solution::new;
+            $crate::testing::assert_listed(env!("CARGO_MANIFEST_DIR"), $day, &[$($file),*]);
+        }
+    };
+}
+
+/// `examples!` for a day in the binary per day layout of `templates/bin.rs`, checking each
+/// listed example with the file's `parse`, `part1` and `part2`.
+/// ```ignore
+/// #[cfg(test)]
+/// mod test {
+///     use super::*;
+///
+///     aoc::bin_examples!(3, { example_1: "1" });
+/// }
+/// ```
+#[macro_export]
+macro_rules! bin_examples {
+    ($day:literal, { $($test:ident: $file:literal),* $(,)? }) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::testing::run_bin_example(
+                    env!("CARGO_MANIFEST_DIR"),
+                    $day,
+                    $file,
+                    |input| parse(input),
+                    |model| part1(model),
+                    |model| part2(model),
+                );
+            }
+        )*
+
+        #[test]
+        fn all_examples_listed() {
+            $crate::testing::assert_listed(env!("CARGO_MANIFEST_DIR"), $day, &[$($file),*]);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::Runner;
+
+    use super::*;
+
+    struct Sum {
+        input: String,
+        values: Vec<i64>,
+    }
+
+    impl Sum {
+        fn new(input: String) -> Self {
+            Self {
+                input,
+                values: Vec::new(),
+            }
+        }
+    }
+
+    impl Runner for Sum {
+        fn name(&self) -> (usize, usize) {
+            (2015, 1)
+        }
+
+        fn parse(&mut self) {
+            self.values = crate::read_numbers(&self.input);
+        }
+
+        fn part1(&mut self) -> String {
+            self.values.iter().sum::<i64>().to_string()
+        }
+
+        fn part2(&mut self) -> String {
+            self.values.iter().product::<i64>().to_string()
+        }
+    }
+
+    #[test]
+    fn test_parse_example() {
+        let example = "part1: 6\npart2: 6\n---\n1\n2\n3\n"
+            .parse::<Example>()
+            .unwrap();
+        assert_eq!(Some("6".into()), example.part1);
+        assert_eq!("1\n2\n3\n", example.input);
+
+        let example = "---\n1\n".parse::<Example>().unwrap();
+        assert_eq!((None, None), (example.part1, example.part2));
+        let example = "part1: 1\r\n---\r\n1\r\n".parse::<Example>().unwrap();
+        assert_eq!(Some("1".into()), example.part1);
+        assert!("1\n2\n".parse::<Example>().is_err());
+        assert!("part3: 1\n---\n1\n".parse::<Example>().is_err());
+
+        for text in [
+            "part1: 6\npart2: 6\n---\n1\n2\n3\n",
+            "part2: 1\n---\n1\n",
+            "---\n",
+        ] {
+            assert_eq!(text, text.parse::<Example>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let example = "part2: 24\n---\n2\n3\n4\n".parse::<Example>().unwrap();
+        assert_eq!(Ok(()), example.check(&mut Sum::new(example.input.clone())));
+
+        let example = "part1: 10\n---\n2\n3\n4\n".parse::<Example>().unwrap();
+        assert_eq!(
+            Err("Part 1: expected `10`, got `9`".into()),
+            example.check(&mut Sum::new(example.input.clone()))
+        );
+    }
+
+    /// Answers with its input text.
+    struct Echo(String);
+
+    impl Runner for Echo {
+        fn name(&self) -> (usize, usize) {
+            (2015, 4)
+        }
+
+        fn parse(&mut self) {
+            self.0 = crate::contents(&self.0).trim().to_string();
+        }
+
+        fn part1(&mut self) -> String {
+            self.0.clone()
+        }
+
+        fn part2(&mut self) -> String {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_example_files() {
+        let dir = std::env::temp_dir().join("aoc_testing_example_files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        for (file, text) in [
+            ("day01-b.txt", "part1: 5\n---\n5\n"),
+            ("day01-a.txt", "part1: 3\npart2: 2\n---\n1\n2\n"),
+            ("day02-a.txt", "---\n"),
+        ] {
+            fs::write(dir.join("examples").join(file), text).unwrap();
+        }
+        let manifest_dir = dir.to_str().unwrap();
+        assert_eq!(vec!["a", "b"], example_names(manifest_dir, 1));
+        run_example(manifest_dir, 1, "a", Sum::new);
+        run_example(manifest_dir, 1, "b", Sum::new);
+        // A one line example that looks like a path is still read as the example.
+        fs::write(dir.join("examples/day04-a.txt"), "part1: 3/4\n---\n3/4\n").unwrap();
+        run_example(manifest_dir, 4, "a", Echo);
+        assert_listed(manifest_dir, 1, &["a", "b"]);
+        assert_listed(manifest_dir, 3, &[]);
+        let failed = std::panic::catch_unwind(|| assert_listed(manifest_dir, 1, &["a"]));
+        let message = *failed.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!("Examples for day 1 with no test: b", message);
+
+        fs::write(dir.join("examples/day02-b.txt"), "part1: 4\n---\n1\n2\n").unwrap();
+        let failed = std::panic::catch_unwind(|| run_example(manifest_dir, 2, "b", Sum::new));
+        let message = *failed.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.ends_with("day02-b.txt: Part 1: expected `4`, got `3`"));
+
+        // Parsing from a file like the binary per day layout.
+        let parse = |file: String| -> Vec<i64> {
+            fs::read_to_string(file)
+                .unwrap()
+                .lines()
+                .map(|l| l.parse().unwrap())
+                .collect()
+        };
+        let sum = |model: &Vec<i64>| model.iter().sum::<i64>();
+        let product = |model: &Vec<i64>| model.iter().product::<i64>();
+        run_bin_example(manifest_dir, 1, "a", parse, sum, product);
+        run_bin_example(manifest_dir, 1, "b", parse, sum, product);
+        let failed =
+            std::panic::catch_unwind(|| run_bin_example(manifest_dir, 2, "b", parse, sum, product));
+        assert!(failed.is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
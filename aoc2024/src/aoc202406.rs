@@ -93,46 +93,5 @@ impl AocDay {
 mod test {
     use super::*;
 
-    #[test]
-    fn test_example1() {
-        let mut day = AocDay::new(
-            "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...
-",
-        );
-        day.parse();
-        let expected = "41";
-        let actual = day.part1();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_example2() {
-        let mut day = AocDay::new(
-            "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...
-",
-        );
-        day.parse();
-        day.part1();
-        let expected = "6";
-        let actual = day.part2();
-        assert_eq!(expected, actual);
-    }
+    aoc::examples!(6, AocDay { example_1: "1" });
 }
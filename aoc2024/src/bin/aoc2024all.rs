@@ -1,47 +1,42 @@
-use std::{io, process::Output, time::Instant};
+use std::{path::Path, process};
+
+use aoc::driver::{Year, release_dir, run_binary, summary};
 
 fn main() {
-    build_days();
-    let start = Instant::now();
-    run_days();
-    let duration = start.elapsed().as_millis();
-    println!("\nTotal: {:3}.{:03}", duration / 1000, duration % 1000);
-}
+    let root = Path::new(".");
+    let year = match Year::load(root, 2024) {
+        Ok(year) => year,
+        Err(e) => {
+            println!("Could not load aoc2024: {e}. Run from the workspace root.");
+            process::exit(2);
+        }
+    };
 
-fn build_days() {
-    for day in 1..=25 {
-        print!("Building day {day:02}..");
-        println!(
-            "{}",
-            match cmd("build", day) {
-                Ok(_) => "Success",
-                Err(_) => "Fail",
-            }
-        );
+    println!("Building aoc2024..");
+    match year.build() {
+        Ok(status) if status.success() => (),
+        Ok(_) => {
+            println!("Build failed.");
+            process::exit(1);
+        }
+        Err(e) => {
+            println!("Could not start cargo: {e}");
+            process::exit(1);
+        }
     }
-}
 
-fn run_days() {
-    for day in 1..=25 {
-        println!(
-            "{}",
-            match cmd("run", day) {
-                Ok(o) => o.stdout.iter().map(|c| char::from(*c)).collect::<String>(),
-                Err(e) => e.to_string(),
-            }
-        )
+    let dir = release_dir(root);
+    let runs = year
+        .binaries()
+        .iter()
+        .map(|(name, args)| {
+            let run = run_binary(&dir, name, args);
+            print!("{}", run.stdout);
+            run
+        })
+        .collect::<Vec<_>>();
+    print!("{}", summary(&runs));
+    if runs.iter().any(|r| !r.success()) {
+        process::exit(1);
     }
 }
-
-fn cmd(cmd: &str, day: usize) -> io::Result<Output> {
-    std::process::Command::new("pwsh")
-        .args([
-            "-command",
-            "cargo",
-            cmd,
-            "--release",
-            "--bin",
-            &format!("aoc2024{day:02}"),
-        ])
-        .output()
-}
@@ -0,0 +1,28 @@
+fn main() {
+    println!("---- {{year}}: {{day02}} ----");
+    let input = "{{input}}";
+    println!("Parsing");
+    let model = parse(input);
+    println!("Part 1: {}", part1(&model));
+    println!("Part 2: {}", part2(&model));
+}
+
+fn parse<S: AsRef<std::path::Path> + std::fmt::Display>(input: S) -> Vec<String> {
+    puzlib::read_lines(input)
+}
+
+fn part1(_model: &[String]) -> String {
+    "Unsolved".into()
+}
+
+fn part2(_model: &[String]) -> String {
+    "Unsolved".into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Save examples as examples/day{{day02}}-NAME.txt and list them here as `test_name: "NAME"`.
+    aoc::bin_examples!({{day}}, {});
+}
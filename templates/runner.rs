@@ -0,0 +1,44 @@
+use aoc::{
+    read_lines,
+    runner::{output, Runner},
+};
+
+#[derive(Default)]
+pub struct AocDay {
+    pub(crate) input: String,
+}
+
+impl AocDay {
+    pub fn new<S: Into<String>>(input: S) -> Self {
+        Self {
+            input: input.into(),
+        }
+    }
+}
+
+impl Runner for AocDay {
+    fn name(&self) -> (usize, usize) {
+        ({{year}}, {{day}})
+    }
+
+    fn parse(&mut self) {
+        // Keep what the parts need in fields of AocDay.
+        let _lines = read_lines(&self.input);
+    }
+
+    fn part1(&mut self) -> String {
+        output("Unsolved")
+    }
+
+    fn part2(&mut self) -> String {
+        output("Unsolved")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Save examples as examples/day{{day02}}-NAME.txt and list them here as `test_name: "NAME"`.
+    aoc::examples!({{day}}, AocDay {});
+}